@@ -2,6 +2,7 @@
 
 use super::*;
 use codec::{Codec, Decode, Encode};
+use frame_support::weights::Weight;
 use sp_runtime::{
 	traits::{Saturating, TrailingZeroInput},
 	ArithmeticError,
@@ -22,7 +23,7 @@ where
 	) -> DispatchResult {
 		let (root_owner, _) = Pallet::<T>::lookup_root_owner(collection_id, nft_id)?;
 		ensure!(sender == root_owner, Error::<T>::NoPermission);
-		// TODO : Check NFT lock status
+		ensure!(!Pallet::<T>::is_nft_locked(collection_id, nft_id), Error::<T>::NftIsLocked);
 		let mut bounded_priorities = Vec::<BoundedVec<u8, T::StringLimit>>::new();
 		for priority in priorities {
 			let bounded_priority = Self::to_bounded_string(priority)?;
@@ -49,7 +50,7 @@ where
 			Collections::<T>::get(&collection_id).ok_or(Error::<T>::NoAvailableCollectionId)?;
 		ensure!(collection.issuer == sender, Error::<T>::NoPermission);
 		if let Some(nft_id) = &maybe_nft_id {
-			// TODO: Check NFT lock status
+			ensure!(!Pallet::<T>::is_nft_locked(collection_id, *nft_id), Error::<T>::NftIsLocked);
 			let (root_owner, _) = Pallet::<T>::lookup_root_owner(collection_id, *nft_id)?;
 			ensure!(root_owner == collection.issuer, Error::<T>::NoPermission);
 		}
@@ -116,7 +117,7 @@ where
 	) -> DispatchResult {
 		let (root_owner, _) = Pallet::<T>::lookup_root_owner(collection_id, nft_id)?;
 		ensure!(root_owner == sender, Error::<T>::NoPermission);
-		// TODO: Check NFT lock status
+		ensure!(!Pallet::<T>::is_nft_locked(collection_id, nft_id), Error::<T>::NftIsLocked);
 
 		Resources::<T>::try_mutate_exists(
 			(collection_id, nft_id, resource_id.clone()),
@@ -290,7 +291,12 @@ where
 		};
 
 		Nfts::<T>::insert(collection_id, nft_id, nft);
-		NftsByOwner::<T>::append(owner, (collection_id, nft_id));
+		NftsByOwner::<T>::insert(&owner, (collection_id, nft_id), ());
+		NftRoots::<T>::insert(
+			collection_id,
+			nft_id,
+			NftRoot { owner, nft: (collection_id, nft_id), depth: 0 },
+		);
 
 		// increment nfts counter
 		let nfts_count = collection.nfts_count.checked_add(1).ok_or(ArithmeticError::Overflow)?;
@@ -309,17 +315,24 @@ where
 		max_recursions: u32,
 	) -> sp_std::result::Result<(CollectionId, NftId), DispatchError> {
 		ensure!(max_recursions > 0, Error::<T>::TooManyRecursions);
+		// Locked NFTs cannot be burned, nor can the NFTs they are nested in
+		ensure!(!Pallet::<T>::is_nft_locked(collection_id, nft_id), Error::<T>::NftIsLocked);
+		if let Ok((root_owner, _)) = Self::lookup_root_owner(collection_id, nft_id) {
+			NftsByOwner::<T>::remove(root_owner, (collection_id, nft_id));
+		}
+		NftRoots::<T>::remove(collection_id, nft_id);
 		Nfts::<T>::remove(collection_id, nft_id);
+		Locks::<T>::remove(collection_id, nft_id);
 
 		for _ in Resources::<T>::drain_prefix((collection_id, nft_id)) {}
 
-		let kids = Children::<T>::take((collection_id, nft_id));
+		let kids = Pallet::<T>::take_children((collection_id, nft_id));
 		for (child_collection_id, child_nft_id) in kids {
-			// Remove child from Children StorageMap
-			Pallet::<T>::remove_child((collection_id, nft_id), (child_collection_id, child_nft_id));
 			Self::nft_burn(child_collection_id, child_nft_id, max_recursions - 1)?;
 		}
 
+		pallet_uniques::Pallet::<T>::do_burn(collection_id, nft_id, |_, _| Ok(()))?;
+
 		// decrement nfts counter
 		Collections::<T>::try_mutate(collection_id, |collection| -> DispatchResult {
 			let collection = collection.as_mut().ok_or(Error::<T>::CollectionUnknown)?;
@@ -327,6 +340,8 @@ where
 			Ok(())
 		})?;
 
+		T::OnNftChange::on_nft_burned(collection_id, nft_id);
+
 		Ok((collection_id, nft_id))
 	}
 
@@ -348,15 +363,20 @@ where
 		let mut sending_nft =
 			Nfts::<T>::get(collection_id, nft_id).ok_or(Error::<T>::NoAvailableNftId)?;
 
-		// TODO: Check NFT lock status
+		// Locked NFTs cannot be sent, nor can the NFTs they are nested in
+		Pallet::<T>::ensure_subtree_unlocked(collection_id, nft_id)?;
 
 		// Needs to be pending if the sending to an account or to a non-owned NFT
 		let mut approval_required = true;
+		// Root of the NFT once sent, `None` while pending
+		let mut new_root = None;
 
 		// Prepare transfer
 		let new_owner_account = match new_owner.clone() {
 			AccountIdOrCollectionNftTuple::AccountId(id) => {
 				approval_required = false;
+				new_root =
+					Some(NftRoot { owner: id.clone(), nft: (collection_id, nft_id), depth: 0 });
 				id
 			},
 			AccountIdOrCollectionNftTuple::CollectionAndNftTuple(cid, nid) => {
@@ -372,9 +392,13 @@ where
 					!Pallet::<T>::is_x_descendent_of_y(cid, nid, collection_id, nft_id),
 					Error::<T>::CannotSendToDescendentOrSelf
 				);
-				let (recipient_root_owner, _root_nft) = Pallet::<T>::lookup_root_owner(cid, nid)?;
-				if recipient_root_owner == root_owner {
+				let recipient_root = Pallet::<T>::root_of(cid, nid)?;
+				if recipient_root.owner == root_owner {
 					approval_required = false;
+					new_root = Some(NftRoot {
+						depth: recipient_root.depth.saturating_add(1),
+						..recipient_root
+					});
 				}
 
 				// Convert to virtual account
@@ -387,11 +411,14 @@ where
 
 		if approval_required {
 			PendingNfts::<T>::insert(collection_id, nft_id, sending_nft);
+			PendingNftSenders::<T>::insert(collection_id, nft_id, &root_owner);
 			Nfts::<T>::remove(collection_id, nft_id);
 		} else {
 			Nfts::<T>::insert(collection_id, nft_id, sending_nft);
 		}
 
+		Pallet::<T>::move_subtree(collection_id, nft_id, Some(root_owner), new_root)?;
+
 		if let Some(current_owner) = parent {
 			// Handle Children StorageMap for NFTs
 			let current_owner_cid_nid =
@@ -407,10 +434,12 @@ where
 			let new_owner_cid_nid =
 				Pallet::<T>::decode_nft_account_id::<T::AccountId>(new_owner_account.clone());
 			if let Some(new_owner_cid_nid) = new_owner_cid_nid {
-				Pallet::<T>::add_child(new_owner_cid_nid, (collection_id, nft_id));
+				Pallet::<T>::add_child(new_owner_cid_nid, (collection_id, nft_id))?;
 			}
 		}
 
+		T::OnNftChange::on_nft_sent(collection_id, nft_id);
+
 		Ok((new_owner_account, approval_required))
 	}
 
@@ -430,8 +459,12 @@ where
 			PendingNfts::<T>::get(collection_id, nft_id).ok_or(Error::<T>::NoAvailableNftId)?;
 
 		// Prepare acceptance
-		let new_owner_account = match new_owner.clone() {
-			AccountIdOrCollectionNftTuple::AccountId(id) => id,
+		let (new_owner_account, new_root) = match new_owner.clone() {
+			AccountIdOrCollectionNftTuple::AccountId(id) => {
+				let new_root =
+					NftRoot { owner: id.clone(), nft: (collection_id, nft_id), depth: 0 };
+				(id, new_root)
+			},
 			AccountIdOrCollectionNftTuple::CollectionAndNftTuple(cid, nid) => {
 				// Check if NFT target exists
 				ensure!(Nfts::<T>::contains_key(cid, nid), Error::<T>::NoAvailableNftId);
@@ -448,25 +481,30 @@ where
 					Error::<T>::CannotSendToDescendentOrSelf
 				);
 
-				let (recipient_root_owner, _root_nft) = Pallet::<T>::lookup_root_owner(cid, nid)?;
-				ensure!(recipient_root_owner == root_owner, Error::<T>::CannotAcceptNonOwnedNft);
+				let recipient_root = Pallet::<T>::root_of(cid, nid)?;
+				ensure!(recipient_root.owner == root_owner, Error::<T>::CannotAcceptNonOwnedNft);
+				let new_root =
+					NftRoot { depth: recipient_root.depth.saturating_add(1), ..recipient_root };
 
 				// Convert to virtual account
-				Pallet::<T>::nft_to_account_id::<T::AccountId>(cid, nid)
+				(Pallet::<T>::nft_to_account_id::<T::AccountId>(cid, nid), new_root)
 			},
 		};
 
 		sending_nft.owner = new_owner;
 		PendingNfts::<T>::remove(collection_id, nft_id);
+		PendingNftSenders::<T>::remove(collection_id, nft_id);
 		Nfts::<T>::insert(collection_id, nft_id, sending_nft);
 
 		// Add child to new parent if NFT virtual address
 		let new_owner_cid_nid =
 			Pallet::<T>::decode_nft_account_id::<T::AccountId>(new_owner_account.clone());
 		if let Some(new_owner_cid_nid) = new_owner_cid_nid {
-			Pallet::<T>::add_child(new_owner_cid_nid, (collection_id, nft_id));
+			Pallet::<T>::add_child(new_owner_cid_nid, (collection_id, nft_id))?;
 		}
 
+		Pallet::<T>::move_subtree(collection_id, nft_id, None, Some(new_root))?;
+
 		Ok((new_owner_account, collection_id, nft_id))
 	}
 
@@ -481,15 +519,90 @@ where
 		ensure!(sender == root_owner, Error::<T>::CannotRejectNonOwnedNft);
 
 		// Get NFT info
-		let mut rejecting_nft =
-			PendingNfts::<T>::get(collection_id, nft_id).ok_or(Error::<T>::NoAvailableNftId)?;
+		let rejecting_nft =
+			PendingNfts::<T>::take(collection_id, nft_id).ok_or(Error::<T>::NoAvailableNftId)?;
+		let previous_owner = PendingNftSenders::<T>::take(collection_id, nft_id);
+
+		if T::ReturnRejectedNfts::get() {
+			// NFTs left pending before senders were recorded cannot be returned, and are not
+			// burned either
+			let previous_owner = previous_owner.ok_or(Error::<T>::UnknownNftSender)?;
+			// Return the NFT, along with its children, to the account that sent it
+			Self::return_pending_nft(collection_id, nft_id, rejecting_nft, previous_owner)?;
+		} else {
+			Self::ensure_subtree_unlocked(collection_id, nft_id)?;
+			Self::nft_burn(collection_id, nft_id, T::MaxRecursions::get())?;
+		}
 
-		PendingNfts::<T>::remove(collection_id, nft_id);
+		Ok((sender, collection_id, nft_id))
+	}
+
+	fn nft_cancel_send(
+		sender: T::AccountId,
+		collection_id: CollectionId,
+		nft_id: NftId,
+	) -> Result<(T::AccountId, CollectionId, NftId), DispatchError> {
+		let pending_sender = PendingNftSenders::<T>::get(collection_id, nft_id)
+			.ok_or(Error::<T>::NoAvailableNftId)?;
+
+		// Only the account that sent the NFT can take it back
+		ensure!(sender == pending_sender, Error::<T>::NoPermission);
+
+		// Get NFT info
+		let cancelled_nft =
+			PendingNfts::<T>::take(collection_id, nft_id).ok_or(Error::<T>::NoAvailableNftId)?;
+		PendingNftSenders::<T>::remove(collection_id, nft_id);
+
+		Self::return_pending_nft(collection_id, nft_id, cancelled_nft, sender.clone())?;
 
 		Ok((sender, collection_id, nft_id))
 	}
 }
 
+impl<T: Config> Lock<T::BlockNumber> for Pallet<T>
+where
+	T: pallet_uniques::Config<ClassId = CollectionId, InstanceId = NftId>,
+{
+	fn lock_nft(
+		collection_id: CollectionId,
+		nft_id: NftId,
+		reason: LockReason,
+		expires: Option<T::BlockNumber>,
+	) -> DispatchResult {
+		ensure!(Nfts::<T>::contains_key(collection_id, nft_id), Error::<T>::NoAvailableNftId);
+		// Only one lock can be held on an NFT at a time
+		ensure!(!Self::is_nft_locked(collection_id, nft_id), Error::<T>::NftIsLocked);
+
+		Locks::<T>::insert(collection_id, nft_id, LockInfo { reason, expires });
+
+		Self::deposit_event(Event::NftLocked { collection_id, nft_id, reason });
+		Ok(())
+	}
+
+	fn unlock_nft(
+		collection_id: CollectionId,
+		nft_id: NftId,
+		reason: LockReason,
+	) -> DispatchResult {
+		Locks::<T>::try_mutate_exists(collection_id, nft_id, |maybe_lock| -> DispatchResult {
+			if let Some(lock) = maybe_lock {
+				// An active lock can only be released for the reason it was taken
+				ensure!(
+					lock.reason == reason || !Self::is_lock_active(lock),
+					Error::<T>::NftIsLocked
+				);
+				*maybe_lock = None;
+				Self::deposit_event(Event::NftUnlocked { collection_id, nft_id });
+			}
+			Ok(())
+		})
+	}
+
+	fn is_nft_locked(collection_id: CollectionId, nft_id: NftId) -> bool {
+		Locks::<T>::get(collection_id, nft_id).map_or(false, |lock| Self::is_lock_active(&lock))
+	}
+}
+
 impl<T: Config> Pallet<T>
 where
 	T: pallet_uniques::Config<ClassId = CollectionId, InstanceId = NftId>,
@@ -557,14 +670,161 @@ where
 		collection_id: CollectionId,
 		nft_id: NftId,
 	) -> Result<(T::AccountId, (CollectionId, NftId)), Error<T>> {
-		let parent = pallet_uniques::Pallet::<T>::owner(collection_id, nft_id);
-		// Check if parent returns None which indicates the NFT is not available
-		parent.as_ref().ok_or(Error::<T>::NoAvailableNftId)?;
-		let owner = parent.unwrap();
+		Self::root_of(collection_id, nft_id).map(|root| (root.owner, root.nft))
+	}
+
+	/// Looks up the root of the ownership tree of an NFT, as stored in the NftRoots StorageMap.
+	/// Pending NFTs and their children are not stored there, their root is found by walking up
+	/// their parents to a stored one.
+	///
+	/// Parameters:
+	/// - `collection_id`: Collection ID of the NFT to lookup the root of
+	/// - `nft_id`: NFT ID that is to be looked up for the root
+	///
+	/// Output:
+	/// - `Result<NftRootOf<T>, Error<T>>`
+	pub fn root_of(collection_id: CollectionId, nft_id: NftId) -> Result<NftRootOf<T>, Error<T>> {
+		if let Some(root) = NftRoots::<T>::get(collection_id, nft_id) {
+			return Ok(root)
+		}
+		let owner = pallet_uniques::Pallet::<T>::owner(collection_id, nft_id)
+			.ok_or(Error::<T>::NoAvailableNftId)?;
 		match Self::decode_nft_account_id::<T::AccountId>(owner.clone()) {
-			None => Ok((owner, (collection_id, nft_id))),
-			Some((cid, nid)) => Pallet::<T>::lookup_root_owner(cid, nid),
+			None => Ok(NftRoot { owner, nft: (collection_id, nft_id), depth: 0 }),
+			Some((cid, nid)) => {
+				let parent_root = Pallet::<T>::root_of(cid, nid)?;
+				Ok(NftRoot { depth: parent_root.depth.saturating_add(1), ..parent_root })
+			},
+		}
+	}
+
+	/// Return a pending NFT, along with its children, to an account
+	///
+	/// Parameters:
+	/// - `collection_id`: Collection ID of the pending NFT
+	/// - `nft_id`: NFT ID of the pending NFT
+	/// - `nft`: NFT info taken out of the PendingNfts StorageMap
+	/// - `owner`: Account receiving the NFT
+	pub fn return_pending_nft(
+		collection_id: CollectionId,
+		nft_id: NftId,
+		mut nft: InstanceInfoOf<T>,
+		owner: T::AccountId,
+	) -> DispatchResult {
+		nft.owner = AccountIdOrCollectionNftTuple::AccountId(owner.clone());
+		Nfts::<T>::insert(collection_id, nft_id, nft);
+		let new_root = NftRoot { owner: owner.clone(), nft: (collection_id, nft_id), depth: 0 };
+		Self::move_subtree(collection_id, nft_id, None, Some(new_root))?;
+		pallet_uniques::Pallet::<T>::do_transfer(
+			collection_id,
+			nft_id,
+			owner,
+			|_class_details, _details| Ok(()),
+		)?;
+		T::OnNftChange::on_nft_sent(collection_id, nft_id);
+		Ok(())
+	}
+
+	/// Worst-case weight of reindexing an NFT and its descendants by their root in the NftRoots
+	/// and NftsByOwner StorageMaps when it is sent, accepted, rejected or returned
+	pub fn reindex_weight() -> Weight {
+		T::DbWeight::get()
+			.reads_writes(1, 3)
+			.saturating_mul(T::MaxSubtreeSize::get() as Weight)
+	}
+
+	/// Move an NFT and its descendants to a new root in the NftRoots and NftsByOwner
+	/// StorageMaps. Fails if any of them would be nested `MaxRecursions` deep or deeper, so that
+	/// they can still be burned, or if there are more than `MaxSubtreeSize` of them.
+	///
+	/// Parameters:
+	/// - `collection_id`: Collection ID of the NFT
+	/// - `nft_id`: NFT ID of the NFT
+	/// - `old_root_owner`: Root owner the NFTs are indexed by, if any
+	/// - `new_root`: Root of the NFT once moved, `None` to drop the NFTs while pending
+	pub fn move_subtree(
+		collection_id: CollectionId,
+		nft_id: NftId,
+		old_root_owner: Option<T::AccountId>,
+		new_root: Option<NftRootOf<T>>,
+	) -> DispatchResult {
+		let max_recursions = T::MaxRecursions::get();
+		let mut remaining = T::MaxSubtreeSize::get();
+		let mut nfts = sp_std::vec![((collection_id, nft_id), new_root)];
+		while let Some((nft, root)) = nfts.pop() {
+			remaining = remaining.checked_sub(1).ok_or(Error::<T>::SubtreeTooLarge)?;
+			if let Some(old_root_owner) = &old_root_owner {
+				NftsByOwner::<T>::remove(old_root_owner, nft);
+			}
+			let child_root = match root {
+				Some(root) => {
+					ensure!(root.depth < max_recursions, Error::<T>::TooManyRecursions);
+					NftsByOwner::<T>::insert(&root.owner, nft, ());
+					NftRoots::<T>::insert(nft.0, nft.1, &root);
+					Some(NftRoot { depth: root.depth + 1, ..root })
+				},
+				None => {
+					NftRoots::<T>::remove(nft.0, nft.1);
+					None
+				},
+			};
+			nfts.extend(
+				Children::<T>::iter_key_prefix(nft).map(|child| (child, child_root.clone())),
+			);
+		}
+		Ok(())
+	}
+
+	/// Ensure neither an NFT nor any of its descendants is locked. Fails if there are more than
+	/// `MaxSubtreeSize` of them, as they could not be sent or burned along with the NFT anyway.
+	///
+	/// Parameters:
+	/// - `collection_id`: Collection ID of the NFT
+	/// - `nft_id`: NFT ID of the NFT
+	pub fn ensure_subtree_unlocked(collection_id: CollectionId, nft_id: NftId) -> DispatchResult {
+		let mut remaining = T::MaxSubtreeSize::get();
+		let mut nfts = sp_std::vec![(collection_id, nft_id)];
+		while let Some(nft) = nfts.pop() {
+			remaining = remaining.checked_sub(1).ok_or(Error::<T>::SubtreeTooLarge)?;
+			ensure!(!Self::is_nft_locked(nft.0, nft.1), Error::<T>::NftIsLocked);
+			nfts.extend(Children::<T>::iter_key_prefix(nft));
 		}
+		Ok(())
+	}
+
+	/// Worst-case weight of checking the locks of an NFT and its descendants
+	pub fn subtree_locks_weight() -> Weight {
+		T::DbWeight::get().reads(2).saturating_mul(T::MaxSubtreeSize::get() as Weight)
+	}
+
+	/// Worst-case weight of burning an NFT and its descendants, at most `MaxSubtreeSize` of them
+	/// as checked by `ensure_subtree_unlocked`, each of them notifying `OnNftChange`
+	pub fn burn_weight() -> Weight {
+		T::DbWeight::get()
+			.reads_writes(2, 5)
+			.saturating_add(T::OnNftChange::weight())
+			.saturating_mul(T::MaxSubtreeSize::get() as Weight)
+	}
+
+	/// NFTs of a root owner, nested NFTs included, a page at a time
+	///
+	/// Parameters:
+	/// - `owner`: Root owner of the NFTs
+	/// - `start_after`: NFT of the previous page to continue after, `None` for the first page
+	/// - `limit`: Maximum number of NFTs to return
+	pub fn nfts_of_owner(
+		owner: &T::AccountId,
+		start_after: Option<(CollectionId, NftId)>,
+		limit: u32,
+	) -> Vec<(CollectionId, NftId)> {
+		let nfts = match start_after {
+			Some(start_after) => NftsByOwner::<T>::iter_key_prefix_from(
+				owner,
+				NftsByOwner::<T>::hashed_key_for(owner, start_after),
+			),
+			None => NftsByOwner::<T>::iter_key_prefix(owner),
+		};
+		nfts.take(limit as usize).collect()
 	}
 
 	/// Add a child to a parent NFT
@@ -575,8 +835,17 @@ where
 	///
 	/// Output:
 	/// - Adding a `child` to the Children StorageMap of the `parent`
-	pub fn add_child(parent: (CollectionId, NftId), child: (CollectionId, NftId)) {
-		Children::<T>::mutate(parent, |v| v.push(child));
+	pub fn add_child(
+		parent: (CollectionId, NftId),
+		child: (CollectionId, NftId),
+	) -> DispatchResult {
+		ChildrenCount::<T>::try_mutate(parent, |count| -> DispatchResult {
+			ensure!(*count < T::MaxChildrenPerNft::get(), Error::<T>::TooManyChildren);
+			*count += 1;
+			Ok(())
+		})?;
+		Children::<T>::insert(parent, child, ());
+		Ok(())
 	}
 
 	/// Remove a child from a parent NFT
@@ -588,9 +857,23 @@ where
 	/// Output:
 	/// - Removing a `child` from the Children StorageMap of the `parent`
 	pub fn remove_child(parent: (CollectionId, NftId), child: (CollectionId, NftId)) {
-		Children::<T>::mutate(parent, |v| {
-			*v = v.iter().filter(|&nft| *nft != child).cloned().collect();
-		});
+		if Children::<T>::take(parent, child).is_some() {
+			ChildrenCount::<T>::mutate_exists(parent, |count| {
+				*count = count.and_then(|count| count.checked_sub(1)).filter(|count| *count > 0);
+			});
+		}
+	}
+
+	/// Remove all children from a parent NFT
+	///
+	/// Parameters:
+	/// - `parent`: Tuple of (CollectionId, NftId) of the parent NFT
+	///
+	/// Output:
+	/// - The children removed from the Children StorageMap of the `parent`
+	pub fn take_children(parent: (CollectionId, NftId)) -> Vec<(CollectionId, NftId)> {
+		ChildrenCount::<T>::remove(parent);
+		Children::<T>::drain_prefix(parent).map(|(child, ())| child).collect()
 	}
 
 	/// Has a child NFT present in the Children StorageMap of the parent NFT
@@ -602,7 +885,7 @@ where
 	/// Output:
 	/// - `bool`
 	pub fn has_child(parent: (CollectionId, NftId)) -> bool {
-		!Children::<T>::get(parent).is_empty()
+		ChildrenCount::<T>::get(parent) > 0
 	}
 
 	/// Check whether a NFT is descends from a suspected parent NFT
@@ -621,30 +904,28 @@ where
 		parent_collection_id: CollectionId,
 		parent_nft_id: NftId,
 	) -> bool {
-		let mut found_child = false;
-
-		let parent = pallet_uniques::Pallet::<T>::owner(child_collection_id, child_nft_id);
-		// Check if parent returns None which indicates the NFT is not available
-		if parent.is_none() {
-			return found_child
+		let (child_root, parent_root) = match (
+			Self::root_of(child_collection_id, child_nft_id),
+			Self::root_of(parent_collection_id, parent_nft_id),
+		) {
+			(Ok(child_root), Ok(parent_root)) => (child_root, parent_root),
+			_ => return false,
+		};
+		// A descendent is nested deeper in the same tree
+		if child_root.nft != parent_root.nft || child_root.depth <= parent_root.depth {
+			return false
 		}
-		let owner = parent.as_ref().unwrap();
-		match Self::decode_nft_account_id::<T::AccountId>(owner.clone()) {
-			None => found_child,
-			Some((cid, nid)) => {
-				if (cid, nid) == (parent_collection_id, parent_nft_id) {
-					found_child = true
-				} else {
-					found_child = Pallet::<T>::is_x_descendent_of_y(
-						cid,
-						nid,
-						parent_collection_id,
-						parent_nft_id,
-					)
-				}
-				found_child
-			},
+		// Walk up as many levels as the NFTs are apart
+		let mut ancestor = (child_collection_id, child_nft_id);
+		for _ in parent_root.depth..child_root.depth {
+			let parent = pallet_uniques::Pallet::<T>::owner(ancestor.0, ancestor.1)
+				.and_then(Self::decode_nft_account_id::<T::AccountId>);
+			match parent {
+				Some(parent) => ancestor = parent,
+				None => return false,
+			}
 		}
+		ancestor == (parent_collection_id, parent_nft_id)
 	}
 
 	/// `recursive_burn` function will recursively call itself to burn the NFT and all the children
@@ -661,13 +942,25 @@ where
 	) -> DispatchResult {
 		ensure!(max_recursions > 0, Error::<T>::TooManyRecursions);
 		Nfts::<T>::remove(collection_id, nft_id);
-		let kids = Children::<T>::take((collection_id, nft_id));
+		let kids = Pallet::<T>::take_children((collection_id, nft_id));
 		for (child_collection_id, child_nft_id) in kids {
 			Pallet::<T>::recursive_burn(child_collection_id, child_nft_id, max_recursions - 1)?;
 		}
 		Ok(())
 	}
 
+	/// Whether a lock still applies at the current block
+	///
+	/// Parameters:
+	/// - `lock`: Lock held on an NFT
+	///
+	/// Output:
+	/// - `bool`
+	pub fn is_lock_active(lock: &LockInfo<T::BlockNumber>) -> bool {
+		lock.expires
+			.map_or(true, |expires| expires > <frame_system::Pallet<T>>::block_number())
+	}
+
 	pub fn to_bounded_string(name: Vec<u8>) -> Result<BoundedVec<u8, T::StringLimit>, Error<T>> {
 		name.try_into().map_err(|_| Error::<T>::TooLong)
 	}
@@ -11,12 +11,13 @@ use sp_runtime::{traits::StaticLookup, DispatchError, Permill};
 use sp_std::{convert::TryInto, vec::Vec};
 
 use rmrk_traits::{
-	primitives::*, AccountIdOrCollectionNftTuple, Collection, CollectionInfo, Nft, NftInfo,
-	Priority, Property, Resource, ResourceInfo,
+	primitives::*, AccountIdOrCollectionNftTuple, Collection, CollectionInfo, Lock, LockInfo,
+	LockReason, Nft, NftInfo, NftRoot, OnNftChange, Priority, Property, Resource, ResourceInfo,
 };
 use sp_std::result::Result;
 
 mod functions;
+pub mod migration;
 
 #[cfg(test)]
 mod mock;
@@ -29,6 +30,8 @@ pub type InstanceInfoOf<T> = NftInfo<
 	BoundedVec<u8, <T as pallet_uniques::Config>::StringLimit>,
 >;
 
+pub type NftRootOf<T> = NftRoot<<T as frame_system::Config>::AccountId>;
+
 pub type BoundedCollectionSymbolOf<T> = BoundedVec<u8, <T as Config>::CollectionSymbolLimit>;
 
 pub type ResourceOf<T, R> =
@@ -65,6 +68,22 @@ pub mod pallet {
 		#[pallet::constant]
 		type ResourceSymbolLimit: Get<u32>;
 		type CollectionSymbolLimit: Get<u32>;
+
+		/// Handler notified of NFTs being sent or burned
+		type OnNftChange: OnNftChange;
+
+		/// Whether rejected NFTs are returned to the account that sent them instead of burned
+		#[pallet::constant]
+		type ReturnRejectedNfts: Get<bool>;
+
+		/// The maximum number of children an NFT can have
+		#[pallet::constant]
+		type MaxChildrenPerNft: Get<u32>;
+
+		/// The maximum number of NFTs moved when an NFT is sent, accepted, rejected or returned,
+		/// or burned, itself and its descendants included
+		#[pallet::constant]
+		type MaxSubtreeSize: Get<u32>;
 	}
 
 	#[pallet::storage]
@@ -91,10 +110,17 @@ pub mod pallet {
 	>;
 
 	#[pallet::storage]
-	#[pallet::getter(fn get_nfts_by_owner)]
-	/// Stores collections info
-	pub type NftsByOwner<T: Config> =
-		StorageMap<_, Twox64Concat, T::AccountId, Vec<(CollectionId, NftId)>>;
+	#[pallet::getter(fn nfts_by_owner)]
+	/// Stores the nfts of each root owner, nested nfts included and pending nfts excluded
+	pub type NftsByOwner<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		Twox64Concat,
+		(CollectionId, NftId),
+		(),
+		OptionQuery,
+	>;
 
 	#[pallet::storage]
 	#[pallet::getter(fn nfts)]
@@ -102,12 +128,24 @@ pub mod pallet {
 	pub type Nfts<T: Config> =
 		StorageDoubleMap<_, Twox64Concat, CollectionId, Twox64Concat, NftId, InstanceInfoOf<T>>;
 
+	#[pallet::storage]
+	#[pallet::getter(fn nft_roots)]
+	/// Stores the root owner and nesting depth of each nft, pending nfts excluded
+	pub type NftRoots<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, CollectionId, Twox64Concat, NftId, NftRootOf<T>>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn pending_nfts)]
 	/// Stores nft info
 	pub type PendingNfts<T: Config> =
 		StorageDoubleMap<_, Twox64Concat, CollectionId, Twox64Concat, NftId, InstanceInfoOf<T>>;
 
+	#[pallet::storage]
+	#[pallet::getter(fn pending_nft_senders)]
+	/// Stores the accounts pending nfts were sent by
+	pub type PendingNftSenders<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, CollectionId, Twox64Concat, NftId, T::AccountId>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn priorities)]
 	/// Stores priority info
@@ -123,8 +161,21 @@ pub mod pallet {
 	#[pallet::storage]
 	#[pallet::getter(fn children)]
 	/// Stores nft children info
-	pub type Children<T: Config> =
-		StorageMap<_, Twox64Concat, (CollectionId, NftId), Vec<(CollectionId, NftId)>, ValueQuery>;
+	pub type Children<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		(CollectionId, NftId),
+		Twox64Concat,
+		(CollectionId, NftId),
+		(),
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn children_count)]
+	/// Stores the number of children of each nft
+	pub type ChildrenCount<T: Config> =
+		StorageMap<_, Twox64Concat, (CollectionId, NftId), u32, ValueQuery>;
 
 	#[pallet::storage]
 	#[pallet::getter(fn resources)]
@@ -154,11 +205,37 @@ pub mod pallet {
 		OptionQuery,
 	>;
 
+	#[pallet::storage]
+	#[pallet::getter(fn locks)]
+	/// Stores the lock held on an nft, if any
+	pub type Locks<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		CollectionId,
+		Twox64Concat,
+		NftId,
+		LockInfo<T::BlockNumber>,
+	>;
+
+	/// The current storage version.
+	pub(crate) const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
+
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	#[pallet::without_storage_info]
 	#[pallet::generate_store(pub(super) trait Store)]
 	pub struct Pallet<T>(_);
 
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T>
+	where
+		T: pallet_uniques::Config<ClassId = CollectionId, InstanceId = NftId>,
+	{
+		fn on_runtime_upgrade() -> Weight {
+			migration::migrate_to_v1::<T>().saturating_add(migration::migrate_to_v2::<T>())
+		}
+	}
+
 	// Pallets use events to inform users when important changes are made.
 	// https://docs.substrate.io/v3/runtime/events-and-errors
 	#[pallet::event]
@@ -200,6 +277,11 @@ pub mod pallet {
 			collection_id: CollectionId,
 			nft_id: NftId,
 		},
+		NFTSendCancelled {
+			sender: T::AccountId,
+			collection_id: CollectionId,
+			nft_id: NftId,
+		},
 		IssuerChanged {
 			old_issuer: T::AccountId,
 			new_issuer: T::AccountId,
@@ -235,6 +317,15 @@ pub mod pallet {
 			collection_id: CollectionId,
 			nft_id: NftId,
 		},
+		NftLocked {
+			collection_id: CollectionId,
+			nft_id: NftId,
+			reason: LockReason,
+		},
+		NftUnlocked {
+			collection_id: CollectionId,
+			nft_id: NftId,
+		},
 	}
 
 	// Errors inform users that something went wrong.
@@ -260,12 +351,20 @@ pub mod pallet {
 		ResourceAlreadyExists,
 		EmptyResource,
 		TooManyRecursions,
+		/// The NFT is locked and cannot be sent, burned or modified
 		NftIsLocked,
 		CannotAcceptNonOwnedNft,
 		CannotRejectNonOwnedNft,
 		ResourceDoesntExist,
 		/// Accepting a resource that is not pending should fail
 		ResourceNotPending,
+		/// The NFT already has the maximum number of children
+		TooManyChildren,
+		/// The account that sent a pending NFT is unknown, so it cannot be returned
+		UnknownNftSender,
+		/// The NFT has more than `MaxSubtreeSize` descendants, itself included, to be moved or
+		/// burned
+		SubtreeTooLarge,
 	}
 
 	#[pallet::call]
@@ -353,7 +452,7 @@ pub mod pallet {
 		}
 
 		/// burn nft
-		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1))]
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1) + Pallet::<T>::subtree_locks_weight() + Pallet::<T>::burn_weight())]
 		#[transactional]
 		pub fn burn_nft(
 			origin: OriginFor<T>,
@@ -364,11 +463,11 @@ pub mod pallet {
 			let (root_owner, _) = Pallet::<T>::lookup_root_owner(collection_id, nft_id)?;
 			// Check ownership
 			ensure!(sender == root_owner, Error::<T>::NoPermission);
+			// Locked NFTs cannot be burned, nor can the NFTs they are nested in
+			Pallet::<T>::ensure_subtree_unlocked(collection_id, nft_id)?;
 			let max_recursions = T::MaxRecursions::get();
 			let (_collection_id, nft_id) = Self::nft_burn(collection_id, nft_id, max_recursions)?;
 
-			pallet_uniques::Pallet::<T>::do_burn(collection_id, nft_id, |_, _| Ok(()))?;
-
 			Self::deposit_event(Event::NFTBurned { owner: sender, nft_id });
 			Ok(())
 		}
@@ -405,7 +504,7 @@ pub mod pallet {
 		/// - `collection_id`: collection id of the nft to be transferred
 		/// - `nft_id`: nft id of the nft to be transferred
 		/// - `new_owner`: new owner of the nft which can be either an account or a NFT
-		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1))]
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1) + Pallet::<T>::subtree_locks_weight() + Pallet::<T>::reindex_weight() + T::OnNftChange::weight())]
 		#[transactional]
 		pub fn send(
 			origin: OriginFor<T>,
@@ -443,7 +542,7 @@ pub mod pallet {
 		/// - `nft_id`: nft id of the nft to be accepted
 		/// - `new_owner`: either origin's account ID or origin-owned NFT, whichever the NFT was
 		///   sent to
-		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1))]
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1) + Pallet::<T>::reindex_weight())]
 		#[transactional]
 		pub fn accept_nft(
 			origin: OriginFor<T>,
@@ -474,11 +573,15 @@ pub mod pallet {
 
 		/// Rejects an NFT sent from another account to self or owned NFT
 		///
+		/// The rejected NFT and its children are burned, or returned to the account that sent
+		/// it if `ReturnRejectedNfts` is set. When returning, rejecting fails if that account is
+		/// unknown.
+		///
 		/// Parameters:
 		/// - `origin`: sender of the transaction
 		/// - `collection_id`: collection id of the nft to be accepted
 		/// - `nft_id`: nft id of the nft to be accepted
-		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1))]
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1) + Pallet::<T>::reindex_weight() + T::OnNftChange::weight() + Pallet::<T>::subtree_locks_weight() + Pallet::<T>::burn_weight())]
 		#[transactional]
 		pub fn reject_nft(
 			origin: OriginFor<T>,
@@ -493,6 +596,28 @@ pub mod pallet {
 			Ok(())
 		}
 
+		/// Cancels a pending send of an NFT, returning it to the sender
+		///
+		/// Parameters:
+		/// - `origin`: sender of the transaction, who sent the pending nft
+		/// - `collection_id`: collection id of the nft to be returned
+		/// - `nft_id`: nft id of the nft to be returned
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1) + Pallet::<T>::reindex_weight() + T::OnNftChange::weight())]
+		#[transactional]
+		pub fn cancel_send(
+			origin: OriginFor<T>,
+			collection_id: CollectionId,
+			nft_id: NftId,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin.clone())?;
+
+			let (sender, collection_id, nft_id) =
+				Self::nft_cancel_send(sender, collection_id, nft_id)?;
+
+			Self::deposit_event(Event::NFTSendCancelled { sender, collection_id, nft_id });
+			Ok(())
+		}
+
 		/// changing the issuer of a collection or a base
 		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1))]
 		#[transactional]
@@ -610,6 +735,7 @@ pub mod pallet {
 
 			let (owner, _) = Pallet::<T>::lookup_root_owner(collection_id, nft_id)?;
 			ensure!(owner == sender, Error::<T>::NoPermission);
+			ensure!(!Pallet::<T>::is_nft_locked(collection_id, nft_id), Error::<T>::NftIsLocked);
 
 			Resources::<T>::try_mutate_exists(
 				(collection_id, nft_id, resource_id.clone()),
@@ -0,0 +1,114 @@
+use super::*;
+use frame_support::{
+	pallet_prelude::*,
+	storage::{
+		migration::{move_prefix, remove_storage_prefix, storage_key_iter},
+		storage_prefix,
+	},
+	traits::{GetStorageVersion, PalletInfoAccess},
+};
+
+/// Moves the children of each NFT from a `Vec` into the `Children` double map, counting them in
+/// `ChildrenCount`
+pub fn migrate_to_v1<T: Config>() -> Weight {
+	if Pallet::<T>::on_chain_storage_version() >= 1 {
+		return T::DbWeight::get().reads(1)
+	}
+
+	// The children of an NFT would be stored under its old key, so move the old entries out of
+	// the way first and drain them from there
+	let pallet_name = <Pallet<T> as PalletInfoAccess>::name().as_bytes();
+	move_prefix(
+		&storage_prefix(pallet_name, b"Children"),
+		&storage_prefix(pallet_name, b"ChildrenV0"),
+	);
+
+	let mut parents = 0u64;
+	let mut writes = 1u64;
+	for (parent, kids) in storage_key_iter::<
+		(CollectionId, NftId),
+		Vec<(CollectionId, NftId)>,
+		Twox64Concat,
+	>(pallet_name, b"ChildrenV0")
+	.drain()
+	{
+		for child in kids.iter() {
+			Children::<T>::insert(parent, child, ());
+		}
+		ChildrenCount::<T>::insert(parent, kids.len() as u32);
+		parents = parents.saturating_add(1);
+		writes = writes.saturating_add(kids.len() as u64 + 1);
+	}
+
+	StorageVersion::new(1).put::<Pallet<T>>();
+	T::DbWeight::get().reads_writes(
+		parents.saturating_mul(2).saturating_add(1),
+		writes.saturating_add(parents.saturating_mul(3)),
+	)
+}
+
+/// Root of an NFT found by walking up the owners of its parents until one of them has its root
+/// stored, `None` if it or any of its parents is pending, along with the number of reads
+fn legacy_root_of<T: Config>(
+	collection_id: CollectionId,
+	nft_id: NftId,
+) -> (Option<NftRootOf<T>>, u64)
+where
+	T: pallet_uniques::Config<ClassId = CollectionId, InstanceId = NftId>,
+{
+	let mut nft = (collection_id, nft_id);
+	let mut depth = 0u32;
+	let mut reads = 0u64;
+	loop {
+		reads = reads.saturating_add(3);
+		if let Some(root) = NftRoots::<T>::get(nft.0, nft.1) {
+			let depth = root.depth.saturating_add(depth);
+			return (Some(NftRoot { depth, ..root }), reads)
+		}
+		if !Nfts::<T>::contains_key(nft.0, nft.1) || depth > T::MaxRecursions::get() {
+			return (None, reads)
+		}
+		let owner = match pallet_uniques::Pallet::<T>::owner(nft.0, nft.1) {
+			Some(owner) => owner,
+			None => return (None, reads),
+		};
+		match Pallet::<T>::decode_nft_account_id::<T::AccountId>(owner.clone()) {
+			None => return (Some(NftRoot { owner, nft, depth }), reads),
+			Some(parent) => {
+				nft = parent;
+				depth = depth.saturating_add(1);
+			},
+		}
+	}
+}
+
+/// Rebuilds `NftsByOwner` from a `Vec` of NFTs per owner into a double map of the NFTs of each
+/// root owner, and stores the root and nesting depth of each NFT in `NftRoots`, in a single
+/// pass over the NFTs. Nested NFTs are included and pending NFTs excluded.
+pub fn migrate_to_v2<T: Config>() -> Weight
+where
+	T: pallet_uniques::Config<ClassId = CollectionId, InstanceId = NftId>,
+{
+	if Pallet::<T>::on_chain_storage_version() >= 2 {
+		return T::DbWeight::get().reads(1)
+	}
+
+	let pallet_name = <Pallet<T> as PalletInfoAccess>::name().as_bytes();
+	remove_storage_prefix(pallet_name, b"NftsByOwner", &[]);
+	remove_storage_prefix(pallet_name, b"NftRoots", &[]);
+
+	let mut reads = 1u64;
+	let mut writes = 3u64;
+	for (collection_id, nft_id, _) in Nfts::<T>::iter() {
+		let (root, root_reads) = legacy_root_of::<T>(collection_id, nft_id);
+		reads = reads.saturating_add(root_reads).saturating_add(1);
+		if let Some(root) = root {
+			NftsByOwner::<T>::insert(&root.owner, (collection_id, nft_id), ());
+			NftRoots::<T>::insert(collection_id, nft_id, root);
+			writes = writes.saturating_add(2);
+		}
+	}
+
+	StorageVersion::new(2).put::<Pallet<T>>();
+	T::DbWeight::get().reads_writes(reads, writes)
+}
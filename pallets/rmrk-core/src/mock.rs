@@ -44,6 +44,9 @@ parameter_types! {
 	pub const MaxRecursions: u32 = 10;
 	pub const ResourceSymbolLimit: u32 = 10;
 	pub const CollectionSymbolLimit: u32 = 100;
+	pub static ReturnRejectedNfts: bool = false;
+	pub const MaxChildrenPerNft: u32 = 2;
+	pub static MaxSubtreeSize: u32 = 20;
 }
 
 impl pallet_rmrk_core::Config for Test {
@@ -53,6 +56,10 @@ impl pallet_rmrk_core::Config for Test {
 	type MaxRecursions = MaxRecursions;
 	type ResourceSymbolLimit = ResourceSymbolLimit;
 	type CollectionSymbolLimit = CollectionSymbolLimit;
+	type OnNftChange = ();
+	type ReturnRejectedNfts = ReturnRejectedNfts;
+	type MaxChildrenPerNft = MaxChildrenPerNft;
+	type MaxSubtreeSize = MaxSubtreeSize;
 }
 
 parameter_types! {
@@ -360,7 +360,7 @@ fn send_nft_to_minted_nft_works() {
 		// Bob-rootowned NFT (0,1) [child] is owned by Bob-rootowned NFT (0,0) [parent]
 		assert_eq!(UNQ::Pallet::<Test>::owner(0, 1), Some(RMRKCore::nft_to_account_id(0, 0)),);
 		// NFT (0,0) has NFT (0,1) in Children StorageMap
-		assert_eq!(RMRKCore::children((0, 0)), vec![(0, 1)]);
+		assert!(RMRKCore::children((0, 0), (0, 1)).is_some());
 		// Attempt to send NFT to self should fail
 		assert_noop!(
 			RMRKCore::send(
@@ -457,6 +457,270 @@ fn reject_nft_works() {
 		));
 		// Bob rejects NFT (0,2) for Bob-owned NFT (0,0)
 		assert_ok!(RMRKCore::reject_nft(Origin::signed(BOB), 0, 2,));
+		// Rejected NFT is burned
+		assert!(RMRKCore::lookup_root_owner(0, 2).is_err());
+		assert!(RMRKCore::pending_nfts(0, 2).is_none());
+		assert_eq!(RMRKCore::collections(COLLECTION_ID_0).unwrap().nfts_count, 2);
+	});
+}
+
+/// NFT: Reject tests, with children (RMRK2.0 spec: new)
+#[test]
+fn reject_nft_burns_children() {
+	ExtBuilder::default().build().execute_with(|| {
+		// Create a basic collection
+		assert_ok!(basic_collection());
+		// Mint NFTs (0, 0), (0, 1), (0, 2), (0, 3)
+		for _ in 0..4 {
+			assert_ok!(basic_mint());
+		}
+		// ALICE sends NFT (0, 0) to BOB
+		assert_ok!(RMRKCore::send(
+			Origin::signed(ALICE),
+			0,
+			0,
+			AccountIdOrCollectionNftTuple::AccountId(BOB),
+		));
+		// ALICE sends NFT (0, 3) to ALICE-owned NFT (0, 2)
+		assert_ok!(RMRKCore::send(
+			Origin::signed(ALICE),
+			0,
+			3,
+			AccountIdOrCollectionNftTuple::CollectionAndNftTuple(0, 2),
+		));
+		// ALICE sends NFT (0, 2) to BOB-owned NFT (0, 0), pending approval
+		assert_ok!(RMRKCore::send(
+			Origin::signed(ALICE),
+			0,
+			2,
+			AccountIdOrCollectionNftTuple::CollectionAndNftTuple(0, 0),
+		));
+		// BOB rejects NFT (0, 2)
+		assert_ok!(RMRKCore::reject_nft(Origin::signed(BOB), 0, 2));
+		// Rejected NFT and its child are burned
+		assert!(RMRKCore::lookup_root_owner(0, 2).is_err());
+		assert!(RMRKCore::lookup_root_owner(0, 3).is_err());
+		assert!(RMRKCore::nfts(0, 3).is_none());
+		assert_eq!(RMRKCore::children_count((0, 2)), 0);
+		assert!(RMRKCore::children((0, 2), (0, 3)).is_none());
+		assert_eq!(RMRKCore::children_count((0, 0)), 0);
+		assert_eq!(RMRKCore::collections(COLLECTION_ID_0).unwrap().nfts_count, 2);
+	});
+}
+
+/// NFT: Reject tests, returning to sender (RMRK2.0 spec: new)
+#[test]
+fn reject_nft_returns_to_sender_when_configured() {
+	ExtBuilder::default().build().execute_with(|| {
+		// Rejected NFTs are returned instead of burned
+		ReturnRejectedNfts::set(&true);
+		// Create a basic collection
+		assert_ok!(basic_collection());
+		// Mint NFTs (0, 0), (0, 1), (0, 2), (0, 3)
+		for _ in 0..4 {
+			assert_ok!(basic_mint());
+		}
+		// ALICE sends NFT (0, 0) to BOB
+		assert_ok!(RMRKCore::send(
+			Origin::signed(ALICE),
+			0,
+			0,
+			AccountIdOrCollectionNftTuple::AccountId(BOB),
+		));
+		// ALICE sends NFT (0, 3) to ALICE-owned NFT (0, 2)
+		assert_ok!(RMRKCore::send(
+			Origin::signed(ALICE),
+			0,
+			3,
+			AccountIdOrCollectionNftTuple::CollectionAndNftTuple(0, 2),
+		));
+		// ALICE sends NFT (0, 2) to BOB-owned NFT (0, 0), pending approval
+		assert_ok!(RMRKCore::send(
+			Origin::signed(ALICE),
+			0,
+			2,
+			AccountIdOrCollectionNftTuple::CollectionAndNftTuple(0, 0),
+		));
+		// BOB rejects NFT (0, 2)
+		assert_ok!(RMRKCore::reject_nft(Origin::signed(BOB), 0, 2));
+		// Rejected NFT is back with ALICE, along with its child
+		assert_eq!(RMRKCore::lookup_root_owner(0, 2).unwrap().0, ALICE);
+		assert_eq!(RMRKCore::lookup_root_owner(0, 3).unwrap().0, ALICE);
+		assert_eq!(
+			RMRKCore::nfts(0, 2).unwrap().owner,
+			AccountIdOrCollectionNftTuple::AccountId(ALICE)
+		);
+		assert!(RMRKCore::pending_nfts(0, 2).is_none());
+		assert!(RMRKCore::children((0, 2), (0, 3)).is_some());
+		assert_eq!(RMRKCore::children_count((0, 2)), 1);
+		assert_eq!(RMRKCore::children_count((0, 0)), 0);
+		assert_eq!(RMRKCore::collections(COLLECTION_ID_0).unwrap().nfts_count, 4);
+	});
+}
+
+/// NFT: Reject tests, pending NFT with an unknown sender (RMRK2.0 spec: new)
+#[test]
+fn reject_nft_with_unknown_sender_fails_when_returning() {
+	ExtBuilder::default().build().execute_with(|| {
+		// Rejected NFTs are returned instead of burned
+		ReturnRejectedNfts::set(&true);
+		// Create a basic collection
+		assert_ok!(basic_collection());
+		// Mint NFTs (0, 0), (0, 1)
+		for _ in 0..2 {
+			assert_ok!(basic_mint());
+		}
+		// ALICE sends NFT (0, 0) to BOB
+		assert_ok!(RMRKCore::send(
+			Origin::signed(ALICE),
+			0,
+			0,
+			AccountIdOrCollectionNftTuple::AccountId(BOB),
+		));
+		// ALICE sends NFT (0, 1) to BOB-owned NFT (0, 0), pending approval
+		assert_ok!(RMRKCore::send(
+			Origin::signed(ALICE),
+			0,
+			1,
+			AccountIdOrCollectionNftTuple::CollectionAndNftTuple(0, 0),
+		));
+		// The sender is unknown, as for NFTs left pending before senders were recorded
+		PendingNftSenders::<Test>::remove(0, 1);
+		// BOB cannot reject NFT (0, 1), as it can neither be returned nor is it burned
+		assert_noop!(
+			RMRKCore::reject_nft(Origin::signed(BOB), 0, 1),
+			Error::<Test>::UnknownNftSender
+		);
+		assert!(RMRKCore::pending_nfts(0, 1).is_some());
+		// BOB can still accept NFT (0, 1)
+		assert_ok!(RMRKCore::accept_nft(
+			Origin::signed(BOB),
+			0,
+			1,
+			AccountIdOrCollectionNftTuple::CollectionAndNftTuple(0, 0),
+		));
+		assert_eq!(RMRKCore::children_count((0, 0)), 1);
+	});
+}
+
+/// NFT: Cancel send tests
+#[test]
+fn cancel_send_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		// Create a basic collection
+		assert_ok!(basic_collection());
+		// Mint NFTs (0, 0), (0, 1)
+		for _ in 0..2 {
+			assert_ok!(basic_mint());
+		}
+		// ALICE sends NFT (0, 0) to BOB
+		assert_ok!(RMRKCore::send(
+			Origin::signed(ALICE),
+			0,
+			0,
+			AccountIdOrCollectionNftTuple::AccountId(BOB),
+		));
+		// ALICE sends NFT (0, 1) to BOB-owned NFT (0, 0), pending approval
+		assert_ok!(RMRKCore::send(
+			Origin::signed(ALICE),
+			0,
+			1,
+			AccountIdOrCollectionNftTuple::CollectionAndNftTuple(0, 0),
+		));
+		// BOB cannot cancel a send made by ALICE
+		assert_noop!(RMRKCore::cancel_send(Origin::signed(BOB), 0, 1), Error::<Test>::NoPermission);
+		// ALICE cancels the send
+		assert_ok!(RMRKCore::cancel_send(Origin::signed(ALICE), 0, 1));
+		System::assert_last_event(MockEvent::RmrkCore(crate::Event::NFTSendCancelled {
+			sender: ALICE,
+			collection_id: 0,
+			nft_id: 1,
+		}));
+		// NFT (0, 1) is back with ALICE
+		assert_eq!(UNQ::Pallet::<Test>::owner(0, 1), Some(ALICE));
+		assert_eq!(
+			RMRKCore::nfts(0, 1).unwrap().owner,
+			AccountIdOrCollectionNftTuple::AccountId(ALICE)
+		);
+		assert!(RMRKCore::pending_nfts(0, 1).is_none());
+		// BOB can no longer accept the NFT
+		assert_noop!(
+			RMRKCore::accept_nft(
+				Origin::signed(BOB),
+				0,
+				1,
+				AccountIdOrCollectionNftTuple::CollectionAndNftTuple(0, 0),
+			),
+			Error::<Test>::NoPermission
+		);
+		// Cancelling again fails as nothing is pending
+		assert_noop!(
+			RMRKCore::cancel_send(Origin::signed(ALICE), 0, 1),
+			Error::<Test>::NoAvailableNftId
+		);
+	});
+}
+
+/// NFT: Owner index tests
+#[test]
+fn nfts_by_owner_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		// Create a basic collection
+		assert_ok!(basic_collection());
+		// Mint NFTs (0, 0), (0, 1), (0, 2), (0, 3)
+		for _ in 0..4 {
+			assert_ok!(basic_mint());
+		}
+		// Minted NFTs are indexed by ALICE
+		let mut nfts = RMRKCore::nfts_of_owner(&ALICE, None, 10);
+		nfts.sort();
+		assert_eq!(nfts, vec![(0, 0), (0, 1), (0, 2), (0, 3)]);
+		// NFTs are returned a page at a time
+		let first_page = RMRKCore::nfts_of_owner(&ALICE, None, 3);
+		assert_eq!(first_page.len(), 3);
+		let second_page = RMRKCore::nfts_of_owner(&ALICE, first_page.last().copied(), 3);
+		assert_eq!(second_page.len(), 1);
+		assert!(!first_page.contains(&second_page[0]));
+		// ALICE sends NFT (0, 1) to ALICE-owned NFT (0, 0), nested NFTs stay indexed by ALICE
+		assert_ok!(RMRKCore::send(
+			Origin::signed(ALICE),
+			0,
+			1,
+			AccountIdOrCollectionNftTuple::CollectionAndNftTuple(0, 0),
+		));
+		assert!(RMRKCore::nfts_by_owner(ALICE, (0, 1)).is_some());
+		// ALICE sends NFT (0, 0) to BOB, along with its child
+		assert_ok!(RMRKCore::send(
+			Origin::signed(ALICE),
+			0,
+			0,
+			AccountIdOrCollectionNftTuple::AccountId(BOB),
+		));
+		let mut nfts = RMRKCore::nfts_of_owner(&BOB, None, 10);
+		nfts.sort();
+		assert_eq!(nfts, vec![(0, 0), (0, 1)]);
+		assert_eq!(RMRKCore::nfts_of_owner(&ALICE, None, 10).len(), 2);
+		// ALICE sends NFT (0, 2) to BOB-owned NFT (0, 0), pending NFTs are not indexed
+		assert_ok!(RMRKCore::send(
+			Origin::signed(ALICE),
+			0,
+			2,
+			AccountIdOrCollectionNftTuple::CollectionAndNftTuple(0, 0),
+		));
+		assert!(RMRKCore::nfts_by_owner(ALICE, (0, 2)).is_none());
+		assert!(RMRKCore::nfts_by_owner(BOB, (0, 2)).is_none());
+		// BOB accepts NFT (0, 2)
+		assert_ok!(RMRKCore::accept_nft(
+			Origin::signed(BOB),
+			0,
+			2,
+			AccountIdOrCollectionNftTuple::CollectionAndNftTuple(0, 0),
+		));
+		assert!(RMRKCore::nfts_by_owner(BOB, (0, 2)).is_some());
+		// BOB burns NFT (0, 0), along with its children
+		assert_ok!(RMRKCore::burn_nft(Origin::signed(BOB), 0, 0));
+		assert!(RMRKCore::nfts_of_owner(&BOB, None, 10).is_empty());
+		assert_eq!(RMRKCore::nfts_of_owner(&ALICE, None, 10), vec![(0, 3)]);
 	});
 }
 
@@ -466,8 +730,8 @@ fn send_two_nfts_to_same_nft_creates_two_children() {
 	ExtBuilder::default().build().execute_with(|| {
 		// Create a basic collection
 		assert_ok!(basic_collection());
-		// Mint NFTs (0, 0), (0, 1), (0, 2)
-		for _ in 0..3 {
+		// Mint NFTs (0, 0), (0, 1), (0, 2), (0, 3)
+		for _ in 0..4 {
 			assert_ok!(basic_mint());
 		}
 		// ALICE sends NFT (0, 1) to NFT (0, 0)
@@ -478,7 +742,8 @@ fn send_two_nfts_to_same_nft_creates_two_children() {
 			AccountIdOrCollectionNftTuple::CollectionAndNftTuple(0, 0),
 		));
 		// NFT (0,0) has NFT (0,1) in Children StorageMap
-		assert_eq!(RMRKCore::children((0, 0)), vec![(0, 1)]);
+		assert!(RMRKCore::children((0, 0), (0, 1)).is_some());
+		assert_eq!(RMRKCore::children_count((0, 0)), 1);
 		// ALICE sends NFT (0, 2) to NFT (0, 0)
 		assert_ok!(RMRKCore::send(
 			Origin::signed(ALICE),
@@ -487,7 +752,19 @@ fn send_two_nfts_to_same_nft_creates_two_children() {
 			AccountIdOrCollectionNftTuple::CollectionAndNftTuple(0, 0),
 		));
 		// NFT (0,0) has NFT (0,1) & (0,2) in Children StorageMap
-		assert_eq!(RMRKCore::children((0, 0)), vec![(0, 1), (0, 2)]);
+		assert!(RMRKCore::children((0, 0), (0, 1)).is_some());
+		assert!(RMRKCore::children((0, 0), (0, 2)).is_some());
+		assert_eq!(RMRKCore::children_count((0, 0)), 2);
+		// Sending a third child to NFT (0, 0) exceeds MaxChildrenPerNft
+		assert_noop!(
+			RMRKCore::send(
+				Origin::signed(ALICE),
+				0,
+				3,
+				AccountIdOrCollectionNftTuple::CollectionAndNftTuple(0, 0),
+			),
+			Error::<Test>::TooManyChildren
+		);
 	});
 }
 
@@ -516,7 +793,8 @@ fn send_nft_removes_existing_parent() {
 			AccountIdOrCollectionNftTuple::CollectionAndNftTuple(0, 0),
 		));
 		// NFT (0, 0) is parent of NFT (0, 1)
-		assert_eq!(RMRKCore::children((0, 0)), vec![(0, 1), (0, 2)]);
+		assert!(RMRKCore::children((0, 0), (0, 1)).is_some());
+		assert_eq!(RMRKCore::children_count((0, 0)), 2);
 		// ALICE sends NFT (0, 1) to NFT (0, 2)
 		assert_ok!(RMRKCore::send(
 			Origin::signed(ALICE),
@@ -525,7 +803,9 @@ fn send_nft_removes_existing_parent() {
 			AccountIdOrCollectionNftTuple::CollectionAndNftTuple(0, 2),
 		));
 		// NFT (0, 0) is no longer parent of NFT (0, 1)
-		assert_eq!(RMRKCore::children((0, 0)), vec![(0, 2)]);
+		assert!(RMRKCore::children((0, 0), (0, 1)).is_none());
+		assert!(RMRKCore::children((0, 2), (0, 1)).is_some());
+		assert_eq!(RMRKCore::children_count((0, 0)), 1);
 	});
 }
 
@@ -633,6 +913,131 @@ fn burn_nft_works() {
 	});
 }
 
+/// NFT: Send tests, cached root owner and nesting depth (RMRK2.0 spec: SEND)
+#[test]
+fn send_nft_updates_roots_and_limits_depth() {
+	ExtBuilder::default().build().execute_with(|| {
+		// Create a collection with max of None
+		assert_ok!(RMRKCore::create_collection(
+			Origin::signed(ALICE),
+			bvec![0u8; 20],
+			None,
+			bvec![0u8; 15]
+		));
+		// Mint NFTs (0, 0) to (0, 10)
+		for _ in 0..11 {
+			assert_ok!(basic_mint());
+		}
+		// Minted NFT is its own root
+		assert_eq!(
+			RMRKCore::nft_roots(0, 0),
+			Some(NftRoot { owner: ALICE, nft: (0, 0), depth: 0 })
+		);
+		// ALICE nests each NFT up to (0, 9) in the previous one
+		for nft_id in 1..10 {
+			assert_ok!(RMRKCore::send(
+				Origin::signed(ALICE),
+				0,
+				nft_id,
+				AccountIdOrCollectionNftTuple::CollectionAndNftTuple(0, nft_id - 1),
+			));
+		}
+		assert_eq!(
+			RMRKCore::nft_roots(0, 9),
+			Some(NftRoot { owner: ALICE, nft: (0, 0), depth: 9 })
+		);
+		// Nesting NFT (0, 10) as deep as MaxRecursions fails
+		assert_noop!(
+			RMRKCore::send(
+				Origin::signed(ALICE),
+				0,
+				10,
+				AccountIdOrCollectionNftTuple::CollectionAndNftTuple(0, 9),
+			),
+			Error::<Test>::TooManyRecursions
+		);
+		// Nesting the chain one level deeper fails as well
+		assert_noop!(
+			RMRKCore::send(
+				Origin::signed(ALICE),
+				0,
+				0,
+				AccountIdOrCollectionNftTuple::CollectionAndNftTuple(0, 10),
+			),
+			Error::<Test>::TooManyRecursions
+		);
+		// Descendents are found within the same tree only
+		assert!(RMRKCore::is_x_descendent_of_y(0, 9, 0, 3));
+		assert!(!RMRKCore::is_x_descendent_of_y(0, 3, 0, 9));
+		assert!(!RMRKCore::is_x_descendent_of_y(0, 9, 0, 10));
+		// ALICE sends NFT (0, 3) to BOB, along with its descendents
+		assert_ok!(RMRKCore::send(
+			Origin::signed(ALICE),
+			0,
+			3,
+			AccountIdOrCollectionNftTuple::AccountId(BOB),
+		));
+		assert_eq!(RMRKCore::nft_roots(0, 9), Some(NftRoot { owner: BOB, nft: (0, 3), depth: 6 }));
+		assert_eq!(RMRKCore::lookup_root_owner(0, 9).unwrap(), (BOB, (0, 3)));
+		assert_eq!(RMRKCore::lookup_root_owner(0, 2).unwrap(), (ALICE, (0, 0)));
+		// Burned NFTs are no longer stored
+		assert_ok!(RMRKCore::burn_nft(Origin::signed(BOB), 0, 3));
+		assert!(RMRKCore::nft_roots(0, 9).is_none());
+		assert!(RMRKCore::lookup_root_owner(0, 9).is_err());
+	});
+}
+
+/// NFT: Send tests, bounded number of descendants (RMRK2.0 spec: SEND)
+#[test]
+fn send_nft_limits_subtree_size() {
+	ExtBuilder::default().build().execute_with(|| {
+		// At most 2 NFTs are moved along with a sent NFT, itself included
+		MaxSubtreeSize::set(&2);
+		// Create a basic collection
+		assert_ok!(basic_collection());
+		// Mint NFTs (0, 0), (0, 1), (0, 2)
+		for _ in 0..3 {
+			assert_ok!(basic_mint());
+		}
+		// ALICE sends NFT (0, 2) to NFT (0, 1), and NFT (0, 1) to NFT (0, 0)
+		assert_ok!(RMRKCore::send(
+			Origin::signed(ALICE),
+			0,
+			2,
+			AccountIdOrCollectionNftTuple::CollectionAndNftTuple(0, 1),
+		));
+		assert_ok!(RMRKCore::send(
+			Origin::signed(ALICE),
+			0,
+			1,
+			AccountIdOrCollectionNftTuple::CollectionAndNftTuple(0, 0),
+		));
+		// Sending NFT (0, 0) along with its 2 descendants fails
+		assert_noop!(
+			RMRKCore::send(
+				Origin::signed(ALICE),
+				0,
+				0,
+				AccountIdOrCollectionNftTuple::AccountId(BOB),
+			),
+			Error::<Test>::SubtreeTooLarge
+		);
+		// Burning NFT (0, 0) along with its 2 descendants fails as well
+		assert_noop!(
+			RMRKCore::burn_nft(Origin::signed(ALICE), 0, 0),
+			Error::<Test>::SubtreeTooLarge
+		);
+		// Sending NFT (0, 1) along with its child works
+		assert_ok!(RMRKCore::send(
+			Origin::signed(ALICE),
+			0,
+			1,
+			AccountIdOrCollectionNftTuple::AccountId(BOB),
+		));
+		assert_eq!(RMRKCore::lookup_root_owner(0, 2).unwrap(), (BOB, (0, 1)));
+	});
+}
+
 /// NFT: Burn complex multi-generational tests (RMRK2.0 spec: BURN)
 #[test]
 fn burn_nft_with_great_grandchildren_works() {
@@ -707,6 +1112,176 @@ fn burn_nft_with_great_grandchildren_works() {
 	});
 }
 
+/// NFT: Lock tests
+#[test]
+fn lock_nft_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		// Create a basic collection
+		assert_ok!(basic_collection());
+		// Mint NFTs (0, 0), (0, 1)
+		for _ in 0..2 {
+			assert_ok!(basic_mint());
+		}
+		// Lock NFT (0, 0) until block 10
+		assert_ok!(RMRKCore::lock_nft(COLLECTION_ID_0, NFT_ID_0, LockReason::Listed, Some(10)));
+		// Locking an NFT should trigger NftLocked event
+		System::assert_last_event(MockEvent::RmrkCore(crate::Event::NftLocked {
+			collection_id: COLLECTION_ID_0,
+			nft_id: NFT_ID_0,
+			reason: LockReason::Listed,
+		}));
+		assert!(RMRKCore::is_nft_locked(COLLECTION_ID_0, NFT_ID_0));
+		// A locked NFT cannot be locked again
+		assert_noop!(
+			RMRKCore::lock_nft(COLLECTION_ID_0, NFT_ID_0, LockReason::Rented, None),
+			Error::<Test>::NftIsLocked
+		);
+		// A non-existent NFT cannot be locked
+		assert_noop!(
+			RMRKCore::lock_nft(COLLECTION_ID_0, 666, LockReason::Listed, None),
+			Error::<Test>::NoAvailableNftId
+		);
+		// Locked NFT cannot be sent to an account
+		assert_noop!(
+			RMRKCore::send(
+				Origin::signed(ALICE),
+				COLLECTION_ID_0,
+				NFT_ID_0,
+				AccountIdOrCollectionNftTuple::AccountId(BOB),
+			),
+			Error::<Test>::NftIsLocked
+		);
+		// Locked NFT cannot be sent to an NFT
+		assert_noop!(
+			RMRKCore::send(
+				Origin::signed(ALICE),
+				COLLECTION_ID_0,
+				NFT_ID_0,
+				AccountIdOrCollectionNftTuple::CollectionAndNftTuple(COLLECTION_ID_0, 1),
+			),
+			Error::<Test>::NftIsLocked
+		);
+		// Locked NFT cannot be burned
+		assert_noop!(
+			RMRKCore::burn_nft(Origin::signed(ALICE), COLLECTION_ID_0, NFT_ID_0),
+			Error::<Test>::NftIsLocked
+		);
+		// Locked NFT cannot have its priorities set
+		assert_noop!(
+			RMRKCore::set_priority(
+				Origin::signed(ALICE),
+				COLLECTION_ID_0,
+				NFT_ID_0,
+				vec![stv("hello"), stv("world")]
+			),
+			Error::<Test>::NftIsLocked
+		);
+		// Locked NFT cannot have its properties set
+		assert_noop!(
+			RMRKCore::set_property(
+				Origin::signed(ALICE),
+				COLLECTION_ID_0,
+				Some(NFT_ID_0),
+				stbk("test-key"),
+				stb("test-value")
+			),
+			Error::<Test>::NftIsLocked
+		);
+		// Lock cannot be released for a different reason
+		assert_noop!(
+			RMRKCore::unlock_nft(COLLECTION_ID_0, NFT_ID_0, LockReason::Rented),
+			Error::<Test>::NftIsLocked
+		);
+		// Lock NFT (0, 1) without expiry, then release it
+		assert_ok!(RMRKCore::lock_nft(COLLECTION_ID_0, 1, LockReason::Custom(7), None));
+		assert_ok!(RMRKCore::unlock_nft(COLLECTION_ID_0, 1, LockReason::Custom(7)));
+		// Unlocking an NFT should trigger NftUnlocked event
+		System::assert_last_event(MockEvent::RmrkCore(crate::Event::NftUnlocked {
+			collection_id: COLLECTION_ID_0,
+			nft_id: 1,
+		}));
+		// Unlocked NFT (0, 1) can be burned again
+		assert_ok!(RMRKCore::burn_nft(Origin::signed(ALICE), COLLECTION_ID_0, 1));
+		// Lock on NFT (0, 0) no longer applies from block 10
+		System::set_block_number(10);
+		assert!(!RMRKCore::is_nft_locked(COLLECTION_ID_0, NFT_ID_0));
+		// NFT (0, 0) can be sent again
+		assert_ok!(RMRKCore::send(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			NFT_ID_0,
+			AccountIdOrCollectionNftTuple::AccountId(BOB),
+		));
+	});
+}
+
+/// NFT: A lock on a nested NFT also applies to the NFTs it is nested in
+#[test]
+fn lock_nested_nft_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		// Create a basic collection
+		assert_ok!(basic_collection());
+		// Mint NFTs (0, 0), (0, 1), (0, 2)
+		for _ in 0..3 {
+			assert_ok!(basic_mint());
+		}
+		// ALICE sends NFT (0, 2) to NFT (0, 1), and NFT (0, 1) to NFT (0, 0)
+		assert_ok!(RMRKCore::send(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			2,
+			AccountIdOrCollectionNftTuple::CollectionAndNftTuple(COLLECTION_ID_0, 1),
+		));
+		assert_ok!(RMRKCore::send(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			1,
+			AccountIdOrCollectionNftTuple::CollectionAndNftTuple(COLLECTION_ID_0, NFT_ID_0),
+		));
+		// Lock the grandchild NFT (0, 2), as if it were listed for sale
+		assert_ok!(RMRKCore::lock_nft(COLLECTION_ID_0, 2, LockReason::Listed, None));
+		assert_noop!(
+			RMRKCore::ensure_subtree_unlocked(COLLECTION_ID_0, NFT_ID_0),
+			Error::<Test>::NftIsLocked
+		);
+		// The root NFT (0, 0) cannot be sent along with the locked NFT
+		assert_noop!(
+			RMRKCore::send(
+				Origin::signed(ALICE),
+				COLLECTION_ID_0,
+				NFT_ID_0,
+				AccountIdOrCollectionNftTuple::AccountId(BOB),
+			),
+			Error::<Test>::NftIsLocked
+		);
+		// The child NFT (0, 1) cannot be sent along with the locked NFT either
+		assert_noop!(
+			RMRKCore::send(
+				Origin::signed(ALICE),
+				COLLECTION_ID_0,
+				1,
+				AccountIdOrCollectionNftTuple::AccountId(BOB),
+			),
+			Error::<Test>::NftIsLocked
+		);
+		// The root NFT (0, 0) cannot be burned along with the locked NFT
+		assert_noop!(
+			RMRKCore::burn_nft(Origin::signed(ALICE), COLLECTION_ID_0, NFT_ID_0),
+			Error::<Test>::NftIsLocked
+		);
+		// The locked NFT is still nested in NFT (0, 1)
+		assert_eq!(
+			UNQ::Pallet::<Test>::owner(COLLECTION_ID_0, 2),
+			Some(RMRKCore::nft_to_account_id(COLLECTION_ID_0, 1))
+		);
+		// Once the lock is released, the root NFT (0, 0) can be burned along with its children
+		assert_ok!(RMRKCore::unlock_nft(COLLECTION_ID_0, 2, LockReason::Listed));
+		assert_ok!(RMRKCore::ensure_subtree_unlocked(COLLECTION_ID_0, NFT_ID_0));
+		assert_ok!(RMRKCore::burn_nft(Origin::signed(ALICE), COLLECTION_ID_0, NFT_ID_0));
+		assert!(RMRKCore::nfts(COLLECTION_ID_0, 2).is_none());
+	});
+}
+
 /// Resource: Basic resource addition (RMRK2.0 spec: RESADD)
 #[test]
 fn create_resource_works() {
@@ -1056,3 +1631,124 @@ fn set_priority_works() {
 		);
 	});
 }
+
+/// Migration: Children moved from a Vec to a double map
+#[test]
+fn migrate_children_to_v1_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		use codec::Encode;
+		use frame_support::{
+			storage::migration::{have_storage_value, put_storage_value},
+			traits::{GetStorageVersion, StorageVersion},
+			StorageHasher, Twox64Concat,
+		};
+		StorageVersion::new(0).put::<RMRKCore>();
+		// NFT (0, 0) has NFTs (0, 1) & (0, 2) as children in the old layout
+		put_storage_value(
+			b"RmrkCore",
+			b"Children",
+			&Twox64Concat::hash(&(0u32, 0u32).encode()),
+			vec![(0u32, 1u32), (0u32, 2u32)],
+		);
+		// Migrate to v1
+		crate::migration::migrate_to_v1::<Test>();
+		// Children are in the double map and counted
+		assert!(RMRKCore::children((0, 0), (0, 1)).is_some());
+		assert!(RMRKCore::children((0, 0), (0, 2)).is_some());
+		assert_eq!(RMRKCore::children_count((0, 0)), 2);
+		// Old entries are drained
+		assert!(!have_storage_value(
+			b"RmrkCore",
+			b"ChildrenV0",
+			&Twox64Concat::hash(&(0u32, 0u32).encode()),
+		));
+		assert_eq!(RMRKCore::on_chain_storage_version(), 1);
+	});
+}
+
+/// Migration: NftsByOwner rebuilt by root owner and NftRoots backfilled
+#[test]
+fn migrate_nfts_by_owner_and_roots_to_v2_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		use codec::Encode;
+		use frame_support::{
+			storage::{
+				migration::{put_storage_value, remove_storage_prefix},
+				storage_prefix, unhashed,
+			},
+			traits::{GetStorageVersion, StorageVersion},
+			StorageHasher, Twox64Concat,
+		};
+		// Create a basic collection
+		assert_ok!(basic_collection());
+		// Mint NFTs (0, 0), (0, 1), (0, 2), (0, 3), (0, 4)
+		for _ in 0..5 {
+			assert_ok!(basic_mint());
+		}
+		// ALICE sends NFT (0, 3) to BOB
+		assert_ok!(RMRKCore::send(
+			Origin::signed(ALICE),
+			0,
+			3,
+			AccountIdOrCollectionNftTuple::AccountId(BOB),
+		));
+		// ALICE sends NFT (0, 2) to NFT (0, 1), and NFT (0, 1) to NFT (0, 0)
+		assert_ok!(RMRKCore::send(
+			Origin::signed(ALICE),
+			0,
+			2,
+			AccountIdOrCollectionNftTuple::CollectionAndNftTuple(0, 1),
+		));
+		assert_ok!(RMRKCore::send(
+			Origin::signed(ALICE),
+			0,
+			1,
+			AccountIdOrCollectionNftTuple::CollectionAndNftTuple(0, 0),
+		));
+		// ALICE sends NFT (0, 4) to BOB-owned NFT (0, 3), pending approval
+		assert_ok!(RMRKCore::send(
+			Origin::signed(ALICE),
+			0,
+			4,
+			AccountIdOrCollectionNftTuple::CollectionAndNftTuple(0, 3),
+		));
+		// NftsByOwner holds a Vec of NFTs per owner in the old layout, and NftRoots is empty
+		StorageVersion::new(1).put::<RMRKCore>();
+		remove_storage_prefix(b"RmrkCore", b"NftsByOwner", &[]);
+		remove_storage_prefix(b"RmrkCore", b"NftRoots", &[]);
+		let old_key = Twox64Concat::hash(&ALICE.encode());
+		put_storage_value(b"RmrkCore", b"NftsByOwner", &old_key, vec![(0u32, 0u32)]);
+		// Migrate to v2
+		crate::migration::migrate_to_v2::<Test>();
+		// NFTs are indexed by root owner, nested NFTs included and pending NFTs excluded
+		let mut nfts = RMRKCore::nfts_of_owner(&ALICE, None, 10);
+		nfts.sort();
+		assert_eq!(nfts, vec![(0, 0), (0, 1), (0, 2)]);
+		assert_eq!(RMRKCore::nfts_of_owner(&BOB, None, 10), vec![(0, 3)]);
+		// Old keys are cleared
+		let mut prefix = storage_prefix(b"RmrkCore", b"NftsByOwner").to_vec();
+		prefix.extend(old_key);
+		assert!(!unhashed::exists(&prefix));
+		// Roots and nesting depths are stored, pending NFTs excluded
+		assert_eq!(
+			RMRKCore::nft_roots(0, 0),
+			Some(NftRoot { owner: ALICE, nft: (0, 0), depth: 0 })
+		);
+		assert_eq!(
+			RMRKCore::nft_roots(0, 2),
+			Some(NftRoot { owner: ALICE, nft: (0, 0), depth: 2 })
+		);
+		assert_eq!(RMRKCore::nft_roots(0, 3), Some(NftRoot { owner: BOB, nft: (0, 3), depth: 0 }));
+		assert!(RMRKCore::nft_roots(0, 4).is_none());
+		// BOB accepts NFT (0, 4), nesting it under the root of NFT (0, 3)
+		assert_ok!(RMRKCore::accept_nft(
+			Origin::signed(BOB),
+			0,
+			4,
+			AccountIdOrCollectionNftTuple::CollectionAndNftTuple(0, 3),
+		));
+		assert_eq!(RMRKCore::nft_roots(0, 4), Some(NftRoot { owner: BOB, nft: (0, 3), depth: 1 }));
+		assert!(RMRKCore::nfts_by_owner(BOB, (0, 4)).is_some());
+		assert_eq!(RMRKCore::on_chain_storage_version(), 2);
+	});
+}
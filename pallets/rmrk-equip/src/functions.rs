@@ -155,6 +155,16 @@ where
 		// Item must exist
 		ensure!(item_exists, Error::<T>::ItemDoesntExist);
 
+		// Neither the item nor the equipper can be locked
+		ensure!(
+			!pallet_rmrk_core::Pallet::<T>::is_nft_locked(item_collection_id, item_nft_id) &&
+				!pallet_rmrk_core::Pallet::<T>::is_nft_locked(
+					equipper_collection_id,
+					equipper_nft_id
+				),
+			Error::<T>::NftIsLocked
+		);
+
 		// Is the item equipped anywhere?
 		ensure!(
 			!pallet_rmrk_core::Pallet::<T>::nfts(item_collection_id, item_nft_id)
@@ -346,3 +356,51 @@ where
 		Ok(())
 	}
 }
+
+impl<T: Config> Unequip for Pallet<T>
+where
+	T: pallet_uniques::Config<ClassId = CollectionId, InstanceId = NftId>,
+{
+	/// Unequip an item from its equipper, which is its direct parent, if it is equipped.
+	/// The slot is found from the item's resources, as Equippings is keyed by the equipper.
+	///
+	/// Parameters:
+	/// - collection_id: Collection ID of the item
+	/// - nft_id: NFT ID of the item
+	fn unequip_nft(collection_id: CollectionId, nft_id: NftId) -> DispatchResult {
+		let nft = match pallet_rmrk_core::Pallet::<T>::nfts(collection_id, nft_id) {
+			Some(nft) if nft.equipped => nft,
+			_ => return Ok(()),
+		};
+		let equipper = match nft.owner {
+			AccountIdOrCollectionNftTuple::CollectionAndNftTuple(cid, nid) => (cid, nid),
+			AccountIdOrCollectionNftTuple::AccountId(_) => return Ok(()),
+		};
+
+		let resources =
+			pallet_rmrk_core::Resources::<T>::iter_prefix_values((collection_id, nft_id));
+		for resource in resources {
+			if let (Some(base_id), Some(slot_id)) = (resource.base, resource.slot) {
+				if Equippings::<T>::get((equipper, base_id, slot_id)).as_ref() == Some(&resource.id)
+				{
+					// Remove from Equippings nft/base/slot storage
+					Equippings::<T>::remove((equipper, base_id, slot_id));
+					Self::deposit_event(Event::SlotUnequipped {
+						item_collection: collection_id,
+						item_nft: nft_id,
+						base_id,
+						slot_id,
+					});
+				}
+			}
+		}
+
+		// Update item's equipped property
+		pallet_rmrk_core::Nfts::<T>::mutate(collection_id, nft_id, |nft| {
+			if let Some(nft) = nft {
+				nft.equipped = false;
+			}
+		});
+		Ok(())
+	}
+}
@@ -10,7 +10,8 @@ use sp_std::vec::Vec;
 pub use pallet::*;
 
 use rmrk_traits::{
-	primitives::*, AccountIdOrCollectionNftTuple, Base, BaseInfo, EquippableList, PartType, Theme,
+	primitives::*, AccountIdOrCollectionNftTuple, Base, BaseInfo, EquippableList, Lock, PartType,
+	Theme, Unequip,
 };
 
 mod functions;
@@ -172,6 +173,8 @@ pub mod pallet {
 		NeedsDefaultThemeFirst,
 		// Equipped item cannot be equipped elsewhere (without first unequipping)
 		AlreadyEquipped,
+		// Locked item or equipper (e.g. listed for sale) cannot take part in equipping
+		NftIsLocked,
 		// Error that should not occur
 		// TODO is this being used?
 		UnknownError,
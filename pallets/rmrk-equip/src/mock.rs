@@ -56,6 +56,9 @@ parameter_types! {
 	pub const MaxRecursions: u32 = 10;
 	pub const ResourceSymbolLimit: u32 = 10;
 	pub const CollectionSymbolLimit: u32 = 100;
+	pub const ReturnRejectedNfts: bool = false;
+	pub const MaxChildrenPerNft: u32 = 20;
+	pub const MaxSubtreeSize: u32 = 50;
 }
 
 impl pallet_rmrk_core::Config for Test {
@@ -65,6 +68,10 @@ impl pallet_rmrk_core::Config for Test {
 	type MaxRecursions = MaxRecursions;
 	type ResourceSymbolLimit = ResourceSymbolLimit;
 	type CollectionSymbolLimit = CollectionSymbolLimit;
+	type OnNftChange = ();
+	type ReturnRejectedNfts = ReturnRejectedNfts;
+	type MaxChildrenPerNft = MaxChildrenPerNft;
+	type MaxSubtreeSize = MaxSubtreeSize;
 }
 
 parameter_types! {
@@ -1,6 +1,6 @@
 use super::*;
 
-use rmrk_traits::{FixedPart, SlotPart, ThemeProperty};
+use rmrk_traits::{FixedPart, LockReason, SlotPart, ThemeProperty, Unequip};
 
 use frame_support::{assert_noop, assert_ok};
 use mock::{Event as MockEvent, *};
@@ -264,6 +264,24 @@ fn equip_works() {
 			None,                                    // parts
 		));
 
+		// Lock the sword, as if it were listed for sale
+		assert_ok!(RmrkCore::lock_nft(1, 0, LockReason::Listed, None));
+
+		// Attempt to equip sword should fail as the sword is locked
+		assert_noop!(
+			RmrkEquip::equip(
+				Origin::signed(ALICE), // Signer
+				(1, 0),                // item
+				(0, 0),                // equipper
+				0,                     // BaseId
+				201,                   // SlotId
+			),
+			Error::<Test>::NftIsLocked
+		);
+
+		// Unlock the sword
+		assert_ok!(RmrkCore::unlock_nft(1, 0, LockReason::Listed));
+
 		// Equipping should now work
 		assert_ok!(RmrkEquip::equip(
 			Origin::signed(ALICE), // Signer
@@ -372,6 +390,18 @@ fn equip_works() {
 			0,                     // BaseId
 			202,                   // SlotId
 		));
+
+		// Unequipping the sword without knowing its slot, as done when it is sold, should work
+		assert_ok!(<RmrkEquip as Unequip>::unequip_nft(1, 0));
+
+		System::assert_last_event(MockEvent::RmrkEquip(crate::Event::SlotUnequipped {
+			item_collection: 1,
+			item_nft: 0,
+			base_id: 0,
+			slot_id: 202,
+		}));
+		assert!(RmrkEquip::equippings(((0, 0), 0, 202)).is_none());
+		assert!(!RmrkCore::nfts(1, 0).unwrap().equipped);
 	});
 }
 /// Base: Basic equip tests
@@ -2,20 +2,36 @@
 #![allow(clippy::unused_unit)]
 #![allow(clippy::upper_case_acronyms)]
 
+use codec::Encode;
 use frame_support::{
 	dispatch::DispatchResult,
 	ensure,
-	traits::{Currency, ExistenceRequirement, ReservableCurrency},
-	transactional, BoundedVec,
+	storage::with_transaction,
+	traits::{
+		tokens::fungibles::{Inspect, Transfer},
+		Currency, ExistenceRequirement, ReservableCurrency,
+	},
+	transactional,
+	weights::Weight,
+	BoundedVec, PalletId,
 };
 use frame_system::{ensure_signed, RawOrigin};
 
+use sp_runtime::{
+	traits::{AccountIdConversion, IdentifyAccount, Saturating, Verify, Zero},
+	PerThing, Perbill, Permill, TransactionOutcome,
+};
 use sp_std::prelude::*;
 
 pub use pallet::*;
 
-use rmrk_traits::{primitives::*, AccountIdOrCollectionNftTuple, NftInfo};
+use pallet_rmrk_core::{KeyLimitOf, ValueLimitOf};
+use rmrk_traits::{
+	primitives::*, AccountIdOrCollectionNftTuple, CollectionStats, Lock, LockReason, Nft, NftInfo,
+	OnNftChange, SaleRecord, Unequip,
+};
 
+pub mod migration;
 pub mod types;
 
 #[cfg(test)]
@@ -24,7 +40,10 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
-use crate::types::Offer;
+use crate::types::{
+	AuctionInfo, BundleId, BundleInfo, CollectionOffer, DutchAuction, MintVoucher, Offer, Order,
+	OrderKind, SwapId, SwapInfo,
+};
 pub use pallet::*;
 
 #[frame_support::pallet]
@@ -46,12 +65,65 @@ pub mod pallet {
 		<T as frame_system::Config>::AccountId,
 		BalanceOf<T>,
 		<T as frame_system::Config>::BlockNumber,
+		<T as Config>::AssetId,
+		BoundedVec<<T as frame_system::Config>::AccountId, <T as Config>::MaxReservedBuyers>,
 	>;
 
 	pub type OfferOf<T> = Offer<
 		<T as frame_system::Config>::AccountId,
 		BalanceOf<T>,
 		<T as frame_system::Config>::BlockNumber,
+		<T as Config>::AssetId,
+	>;
+
+	pub type CollectionOfferOf<T> = CollectionOffer<
+		<T as frame_system::Config>::AccountId,
+		BalanceOf<T>,
+		<T as frame_system::Config>::BlockNumber,
+		<T as Config>::AssetId,
+		KeyLimitOf<T>,
+		ValueLimitOf<T>,
+	>;
+
+	pub type BundleInfoOf<T> = BundleInfo<
+		<T as frame_system::Config>::AccountId,
+		BalanceOf<T>,
+		<T as frame_system::Config>::BlockNumber,
+		<T as Config>::AssetId,
+		BoundedVec<(CollectionId, NftId), <T as Config>::MaxBundleSize>,
+	>;
+
+	pub type SwapInfoOf<T> = SwapInfo<
+		<T as frame_system::Config>::AccountId,
+		BalanceOf<T>,
+		<T as Config>::AssetId,
+		BoundedVec<(CollectionId, NftId), <T as Config>::MaxBundleSize>,
+	>;
+
+	pub type MintVoucherOf<T> = MintVoucher<
+		BalanceOf<T>,
+		<T as frame_system::Config>::BlockNumber,
+		<T as Config>::AssetId,
+		BoundedVec<u8, <T as pallet_uniques::Config>::StringLimit>,
+	>;
+
+	pub type OrderOf<T> = Order<
+		<T as frame_system::Config>::AccountId,
+		BalanceOf<T>,
+		<T as frame_system::Config>::BlockNumber,
+		<T as Config>::AssetId,
+	>;
+
+	pub type SaleRecordOf<T> = SaleRecord<BalanceOf<T>, <T as frame_system::Config>::BlockNumber>;
+
+	pub type CollectionStatsOf<T> =
+		CollectionStats<BalanceOf<T>, BoundedVec<SaleRecordOf<T>, <T as Config>::MaxRecentSales>>;
+
+	pub type AuctionInfoOf<T> = AuctionInfo<
+		<T as frame_system::Config>::AccountId,
+		BalanceOf<T>,
+		<T as frame_system::Config>::BlockNumber,
+		<T as Config>::AssetId,
 	>;
 
 	/// Configure the pallet by specifying the parameters and types on which it depends.
@@ -66,15 +138,76 @@ pub mod pallet {
 		/// The market currency mechanism.
 		type Currency: ReservableCurrency<Self::AccountId>;
 
+		/// Identifier of the assets, next to the native currency, NFTs can be traded in
+		type AssetId: Member + Parameter + Copy + MaxEncodedLen;
+
+		/// The assets NFTs can be traded in next to the native currency
+		type Assets: Transfer<Self::AccountId, AssetId = Self::AssetId, Balance = BalanceOf<Self>>;
+
+		/// Unequips nested NFTs when they are sold
+		type Equip: Unequip;
+
+		/// The market's pallet id. Its sub-accounts hold the NFTs listed in escrow, and offers and
+		/// bids made in assets, as assets cannot be reserved.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
+		/// Signature of mint vouchers and orders signed off-chain
+		type OffchainSignature: Verify<Signer = Self::OffchainPublic> + Parameter;
+
+		/// Public key of an off-chain signer, identifying its account
+		type OffchainPublic: IdentifyAccount<AccountId = Self::AccountId>;
+
 		/// Minimum offer amount as a valid offer
 		#[pallet::constant]
 		type MinimumOfferAmount: Get<BalanceOf<Self>>;
 
+		/// Maximum number of offers on an NFT at a time
+		#[pallet::constant]
+		type MaxOffersPerNft: Get<u32>;
+
+		/// Default marketplace fee charged on every sale, until changed through `set_fee`
+		#[pallet::constant]
+		type MarketplaceFee: Get<Permill>;
+
+		/// Account receiving the marketplace fees
+		#[pallet::constant]
+		type MarketplaceFeeAccount: Get<Self::AccountId>;
+
+		/// A bid placed within this many blocks of the end of an auction extends the auction to
+		/// end this many blocks after the bid
+		#[pallet::constant]
+		type AuctionExtensionPeriod: Get<Self::BlockNumber>;
+
+		/// Maximum number of auctions that can end in the same block
+		#[pallet::constant]
+		type MaxAuctionsPerBlock: Get<u32>;
+
+		/// Maximum number of listings and offers checked for expiry in a block
+		#[pallet::constant]
+		type MaxSweepsPerBlock: Get<u32>;
+
+		/// Maximum number of NFTs sold together in a bundle, or offered and requested in a swap
+		#[pallet::constant]
+		type MaxBundleSize: Get<u32>;
+
+		/// Maximum number of buyers a private listing can be reserved for
+		#[pallet::constant]
+		type MaxReservedBuyers: Get<u32>;
+
+		/// Maximum number of recent sales kept in the statistics of a collection
+		#[pallet::constant]
+		type MaxRecentSales: Get<u32>;
+
 		// TODO: Weight values for this pallet
 		// type WeightInfo: WeightInfo;
 	}
 
+	/// The current storage version.
+	pub(crate) const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	#[pallet::generate_store(pub(super) trait Store)]
 	pub struct Pallet<T>(_);
 
@@ -104,6 +237,165 @@ pub mod pallet {
 		OptionQuery,
 	>;
 
+	#[pallet::storage]
+	#[pallet::getter(fn offers_count)]
+	/// Stores the number of offers on a NFT
+	pub type OffersCount<T: Config> =
+		StorageMap<_, Blake2_128Concat, (CollectionId, NftId), u32, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn collection_offers)]
+	/// Stores offers on any NFT of a collection
+	pub type CollectionOffers<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		CollectionId,
+		Blake2_128Concat,
+		T::AccountId,
+		CollectionOfferOf<T>,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn marketplace_fee)]
+	/// Stores the marketplace fee charged on every sale
+	pub type MarketplaceFee<T: Config> = StorageValue<_, Permill, ValueQuery, T::MarketplaceFee>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn auctions)]
+	/// Stores English auctions on NFTs
+	pub type Auctions<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		CollectionId,
+		Blake2_128Concat,
+		NftId,
+		AuctionInfoOf<T>,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn auctions_ending_at)]
+	/// Stores the auctions to settle at a block
+	pub type AuctionsEndingAt<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::BlockNumber,
+		BoundedVec<(CollectionId, NftId), T::MaxAuctionsPerBlock>,
+		ValueQuery,
+	>;
+
+	#[pallet::storage]
+	/// Stores the last listing checked for expiry, the next sweep continues after it
+	pub type ListingsSweepCursor<T: Config> = StorageValue<_, (CollectionId, NftId), OptionQuery>;
+
+	#[pallet::storage]
+	/// Stores the last offer checked for expiry, the next sweep continues after it
+	pub type OffersSweepCursor<T: Config> =
+		StorageValue<_, ((CollectionId, NftId), T::AccountId), OptionQuery>;
+
+	#[pallet::storage]
+	/// Stores the last collection offer checked for expiry, the next sweep continues after it
+	pub type CollectionOffersSweepCursor<T: Config> =
+		StorageValue<_, (CollectionId, T::AccountId), OptionQuery>;
+
+	#[pallet::storage]
+	/// Stores the last bundle checked for expiry, the next sweep continues after it
+	pub type BundlesSweepCursor<T: Config> = StorageValue<_, BundleId, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn next_bundle_id)]
+	/// Stores the id of the next bundle
+	pub type NextBundleId<T: Config> = StorageValue<_, BundleId, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn bundles)]
+	/// Stores bundles of NFTs listed for a single price
+	pub type Bundles<T: Config> =
+		StorageMap<_, Twox64Concat, BundleId, BundleInfoOf<T>, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn bundled_nfts)]
+	/// Stores the bundle a listed NFT is part of
+	pub type BundledNfts<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		CollectionId,
+		Blake2_128Concat,
+		NftId,
+		BundleId,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn next_swap_id)]
+	/// Stores the id of the next swap
+	pub type NextSwapId<T: Config> = StorageValue<_, SwapId, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn swaps)]
+	/// Stores proposed swaps of NFTs between two accounts
+	pub type Swaps<T: Config> = StorageMap<_, Twox64Concat, SwapId, SwapInfoOf<T>, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn redeemed_vouchers)]
+	/// Stores the nonces of the mint vouchers redeemed, per issuer
+	pub type RedeemedVouchers<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Twox64Concat, u64, (), OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn used_order_nonces)]
+	/// Stores the nonces of the orders filled or cancelled, per maker
+	pub type UsedOrderNonces<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Twox64Concat, u64, (), OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn min_order_nonce)]
+	/// Stores the lowest nonce of the valid orders of a maker
+	pub type MinOrderNonce<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, u64, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn is_market_halted)]
+	/// Stores whether trading is halted on the whole marketplace
+	pub type MarketHalted<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn paused_collections)]
+	/// Stores the collections whose trading is paused
+	pub type PausedCollections<T: Config> =
+		StorageMap<_, Blake2_128Concat, CollectionId, (), OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn escrowed_nfts)]
+	/// Stores the NFTs held in escrow while listed, by the seller they belong to
+	pub type EscrowedNfts<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		(CollectionId, NftId),
+		(),
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	/// Stores the NFT being sent into or out of escrow for its seller, only set during the send
+	pub type EscrowTransfer<T: Config> = StorageValue<_, (CollectionId, NftId), OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn collection_stats)]
+	/// Stores the sale statistics of a collection, per currency the sales were made in
+	pub type Stats<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		CollectionId,
+		Blake2_128Concat,
+		Option<T::AssetId>,
+		CollectionStatsOf<T>,
+		OptionQuery,
+	>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
@@ -128,6 +420,7 @@ pub mod pallet {
 			collection_id: CollectionId,
 			nft_id: NftId,
 			price: BalanceOf<T>,
+			asset_id: Option<T::AssetId>,
 		},
 		/// Token unlisted on Marketplace
 		TokenUnlisted { owner: T::AccountId, collection_id: CollectionId, nft_id: NftId },
@@ -137,6 +430,15 @@ pub mod pallet {
 			collection_id: CollectionId,
 			nft_id: NftId,
 			price: BalanceOf<T>,
+			asset_id: Option<T::AssetId>,
+		},
+		/// Offer was raised or lowered
+		OfferUpdated {
+			offerer: T::AccountId,
+			collection_id: CollectionId,
+			nft_id: NftId,
+			price: BalanceOf<T>,
+			expires: Option<T::BlockNumber>,
 		},
 		/// Offer was withdrawn
 		OfferWithdrawn { sender: T::AccountId, collection_id: CollectionId, nft_id: NftId },
@@ -147,6 +449,150 @@ pub mod pallet {
 			collection_id: CollectionId,
 			nft_id: NftId,
 		},
+		/// Royalty share of a sale was paid to the NFT's royalty recipient
+		RoyaltyPaid {
+			recipient: T::AccountId,
+			collection_id: CollectionId,
+			nft_id: NftId,
+			amount: BalanceOf<T>,
+		},
+		/// Marketplace fee of a sale was paid to the fee account
+		MarketplaceFeePaid { collection_id: CollectionId, nft_id: NftId, amount: BalanceOf<T> },
+		/// Marketplace fee was changed
+		MarketplaceFeeSet { fee: Permill },
+		/// Token was minted from a mint voucher of the collection issuer
+		VoucherRedeemed {
+			issuer: T::AccountId,
+			buyer: T::AccountId,
+			collection_id: CollectionId,
+			nft_id: NftId,
+			price: BalanceOf<T>,
+			nonce: u64,
+		},
+		/// Order signed off-chain was filled
+		OrderFilled {
+			maker: T::AccountId,
+			taker: T::AccountId,
+			collection_id: CollectionId,
+			nft_id: NftId,
+			price: BalanceOf<T>,
+			nonce: u64,
+		},
+		/// Order signed off-chain was cancelled
+		OrderCancelled { maker: T::AccountId, nonce: u64 },
+		/// Orders signed off-chain with a nonce below `min_nonce` were cancelled
+		OrderNonceBumped { maker: T::AccountId, min_nonce: u64 },
+		/// Trading on the whole marketplace was halted or resumed
+		MarketHaltSet { halted: bool },
+		/// Trading of a collection was paused or resumed
+		CollectionPauseSet { collection_id: CollectionId, paused: bool },
+		/// Token listed on Marketplace for a set of buyers only
+		TokenListedPrivately {
+			owner: T::AccountId,
+			collection_id: CollectionId,
+			nft_id: NftId,
+			price: BalanceOf<T>,
+			asset_id: Option<T::AssetId>,
+			buyers: Vec<T::AccountId>,
+		},
+		/// Token listed on Marketplace and held in escrow until sold or unlisted
+		TokenListedEscrowed {
+			owner: T::AccountId,
+			collection_id: CollectionId,
+			nft_id: NftId,
+			price: BalanceOf<T>,
+			asset_id: Option<T::AssetId>,
+		},
+		/// Token listed on Marketplace with a declining price
+		TokenListedDutch {
+			owner: T::AccountId,
+			collection_id: CollectionId,
+			nft_id: NftId,
+			start_price: BalanceOf<T>,
+			floor_price: BalanceOf<T>,
+			start: T::BlockNumber,
+			end: T::BlockNumber,
+			asset_id: Option<T::AssetId>,
+		},
+		/// Token was put up for auction
+		AuctionCreated {
+			owner: T::AccountId,
+			collection_id: CollectionId,
+			nft_id: NftId,
+			reserve_price: BalanceOf<T>,
+			end: T::BlockNumber,
+			asset_id: Option<T::AssetId>,
+		},
+		/// Bid was placed on an auction
+		BidPlaced {
+			bidder: T::AccountId,
+			collection_id: CollectionId,
+			nft_id: NftId,
+			amount: BalanceOf<T>,
+		},
+		/// Auction was extended by a late bid
+		AuctionExtended { collection_id: CollectionId, nft_id: NftId, end: T::BlockNumber },
+		/// Auction ended and the token was sold to the highest bidder
+		AuctionSettled {
+			owner: T::AccountId,
+			winner: T::AccountId,
+			collection_id: CollectionId,
+			nft_id: NftId,
+			price: BalanceOf<T>,
+		},
+		/// Auction was cancelled or ended without a sale
+		AuctionCancelled { collection_id: CollectionId, nft_id: NftId },
+		/// Auction ended while trading was halted or paused, it is settled by `settle_auction`
+		AuctionSettlementDeferred { collection_id: CollectionId, nft_id: NftId },
+		/// Expired listing was removed
+		ListingExpired { owner: T::AccountId, collection_id: CollectionId, nft_id: NftId },
+		/// Expired offer was removed and its amount unreserved
+		OfferExpired { offerer: T::AccountId, collection_id: CollectionId, nft_id: NftId },
+		/// Offer was removed and its amount unreserved because the token was sent or burned
+		OfferRefunded { offerer: T::AccountId, collection_id: CollectionId, nft_id: NftId },
+		/// Offer was placed on any token of a collection
+		CollectionOfferPlaced {
+			offerer: T::AccountId,
+			collection_id: CollectionId,
+			price: BalanceOf<T>,
+			asset_id: Option<T::AssetId>,
+		},
+		/// Collection offer was withdrawn
+		CollectionOfferWithdrawn { offerer: T::AccountId, collection_id: CollectionId },
+		/// Expired collection offer was removed and its amount unreserved
+		CollectionOfferExpired { offerer: T::AccountId, collection_id: CollectionId },
+		/// Collection offer was accepted for a token
+		CollectionOfferAccepted {
+			owner: T::AccountId,
+			buyer: T::AccountId,
+			collection_id: CollectionId,
+			nft_id: NftId,
+		},
+		/// Bundle of tokens listed on Marketplace
+		BundleListed {
+			owner: T::AccountId,
+			bundle_id: BundleId,
+			price: BalanceOf<T>,
+			asset_id: Option<T::AssetId>,
+		},
+		/// Bundle was unlisted, or invalidated because one of its tokens was sent, burned or
+		/// listed separately
+		BundleUnlisted { owner: T::AccountId, bundle_id: BundleId },
+		/// Expired bundle was removed
+		BundleExpired { owner: T::AccountId, bundle_id: BundleId },
+		/// Bundle of tokens was sold to a new owner
+		BundleSold {
+			owner: T::AccountId,
+			buyer: T::AccountId,
+			bundle_id: BundleId,
+			price: BalanceOf<T>,
+		},
+		/// Swap of tokens was proposed to a counterparty
+		SwapProposed { proposer: T::AccountId, counterparty: T::AccountId, swap_id: SwapId },
+		/// Swap was accepted and the tokens exchanged
+		SwapAccepted { proposer: T::AccountId, counterparty: T::AccountId, swap_id: SwapId },
+		/// Swap was cancelled by the proposer or rejected by the counterparty
+		SwapCancelled { swap_id: SwapId },
 	}
 
 	// Errors inform users that something went wrong.
@@ -180,6 +626,158 @@ pub mod pallet {
 		ListingHasExpired,
 		/// Price differs from when `buy` was executed
 		PriceDiffersFromExpected,
+		/// Auction is unknown
+		UnknownAuction,
+		/// Auction has ended and cannot be bid on
+		AuctionHasEnded,
+		/// Auction with bids cannot be cancelled
+		AuctionHasBids,
+		/// Auction has not ended yet and cannot be settled
+		AuctionNotEnded,
+		/// Auction duration must be greater than zero
+		InvalidAuctionDuration,
+		/// Bid is below the reserve price or the minimum increment over the highest bid
+		BidTooLow,
+		/// Too many auctions end in the same block
+		TooManyAuctionsEnding,
+		/// Token is in an auction and cannot be sold otherwise
+		TokenInAuction,
+		/// Dutch auction must end after it starts and its floor price cannot exceed its start
+		/// price
+		InvalidDutchAuction,
+		/// Current price of a Dutch auction is above the buyer's maximum price
+		PriceAboveMaximum,
+		/// Cannot make a collection offer on a non-existing collection
+		CollectionDoesNotExist,
+		/// NFT does not have the property required by the collection offer
+		PropertyMismatch,
+		/// Bundle is unknown
+		UnknownBundle,
+		/// Bundle must contain at least one NFT
+		EmptyBundle,
+		/// Bundle contains more than `MaxBundleSize` NFTs
+		BundleTooLarge,
+		/// Bundle contains the same NFT twice
+		DuplicateBundleItem,
+		/// Bundle contains an NFT nested in another NFT of the bundle
+		NestedBundleItem,
+		/// No more bundle ids available
+		NoAvailableBundleId,
+		/// Swap is unknown
+		UnknownSwap,
+		/// Swap must request at least one NFT and offer NFTs or an amount
+		EmptySwap,
+		/// Swap offers or requests more than `MaxBundleSize` NFTs
+		SwapTooLarge,
+		/// Swap offers or requests the same NFT twice
+		DuplicateSwapItem,
+		/// Swap offers or requests an NFT nested in another NFT it offers or requests
+		NestedSwapItem,
+		/// Cannot propose a swap to self
+		CannotSwapWithSelf,
+		/// No more swap ids available
+		NoAvailableSwapId,
+		/// Private listing has no buyers to reserve it for
+		NoReservedBuyers,
+		/// Private listing is reserved for more than `MaxReservedBuyers` buyers
+		TooManyReservedBuyers,
+		/// NFT has the maximum number of offers
+		TooManyOffers,
+		/// Private listing is not reserved for the buyer
+		NotReservedBuyer,
+		/// Trading is halted on the whole marketplace
+		MarketHalted,
+		/// Trading of the collection is paused
+		CollectionPaused,
+		/// Mint voucher or order is not signed by the collection issuer or order maker
+		InvalidSignature,
+		/// Mint voucher has expired
+		VoucherExpired,
+		/// Mint voucher was already redeemed
+		VoucherAlreadyRedeemed,
+		/// Order has expired
+		OrderExpired,
+		/// Order was already filled or cancelled
+		InvalidOrderNonce,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T>
+	where
+		T: pallet_uniques::Config<ClassId = CollectionId, InstanceId = NftId>,
+	{
+		/// Settle the auctions ending at this block. Auctions of NFTs that cannot be traded
+		/// because the market is halted or their collection paused are left to `settle_auction`.
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			let ending = AuctionsEndingAt::<T>::take(now);
+			let settled = ending.len() as Weight;
+			for (collection_id, nft_id) in ending {
+				if Self::ensure_trading(collection_id).is_ok() {
+					Self::finish_auction(collection_id, nft_id);
+				} else {
+					Self::deposit_event(Event::AuctionSettlementDeferred { collection_id, nft_id });
+				}
+			}
+
+			T::DbWeight::get()
+				.reads_writes(1, 1)
+				.saturating_add(settled.saturating_mul(10_000))
+				.saturating_add(
+					T::DbWeight::get()
+						.reads_writes(8, 8)
+						.saturating_add(Self::nft_change_weight())
+						.saturating_mul(settled),
+				)
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			migration::migrate_to_v1::<T>()
+		}
+
+		/// Remove expired listings, offers, collection offers and bundles with the weight left in
+		/// the block
+		fn on_idle(now: T::BlockNumber, remaining_weight: Weight) -> Weight {
+			// Reading and writing all cursors
+			let base_weight = T::DbWeight::get().reads_writes(4, 4);
+			// Reading a listing, removing it, unlocking the NFT and returning an escrowed NFT
+			let listing_weight =
+				T::DbWeight::get().reads_writes(4, 5).saturating_add(Self::nft_change_weight());
+			// Reading an offer, removing it and unreserving its amount
+			let offer_weight = T::DbWeight::get().reads_writes(2, 3);
+			// Reading a bundle, removing it and unlocking each of its NFTs
+			let bundle_weight = T::DbWeight::get()
+				.reads_writes(2, 2)
+				.saturating_mul(T::MaxBundleSize::get() as Weight + 1);
+			if remaining_weight < base_weight.saturating_add(offer_weight) {
+				return 0
+			}
+
+			let sweeps: [(fn(T::BlockNumber, usize) -> usize, Weight); 4] = [
+				(Self::sweep_expired_listings, listing_weight),
+				(Self::sweep_expired_offers, offer_weight),
+				(Self::sweep_expired_collection_offers, offer_weight),
+				(Self::sweep_expired_bundles, bundle_weight),
+			];
+			// Keep a share of the limit for each following kind of entry so none is starved, a
+			// share left unused carries over to the next kind
+			let kinds = sweeps.len();
+			let mut left = T::MaxSweepsPerBlock::get() as usize;
+			let share = left / kinds;
+			let mut used_weight = base_weight;
+			for (index, (sweep, entry_weight)) in sweeps.into_iter().enumerate() {
+				let kept = share * (kinds - 1 - index);
+				let limit = remaining_weight
+					.saturating_sub(used_weight)
+					.checked_div(entry_weight)
+					.unwrap_or(Weight::MAX)
+					.min(left.saturating_sub(kept) as Weight) as usize;
+				let swept = sweep(now, limit);
+				left = left.saturating_sub(swept);
+				used_weight =
+					used_weight.saturating_add(entry_weight.saturating_mul(swept as Weight));
+			}
+			used_weight
+		}
 	}
 
 	#[pallet::call]
@@ -194,30 +792,39 @@ pub mod pallet {
 		/// 	- `origin` - Account of the potential buyer
 		/// 	- `collection_id` - Collection id of the RMRK NFT
 		/// 	- `nft_id` - NFT id of the RMRK NFT
-		/// - `amount` - Optional price at which buyer purchased at
-		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1))]
+		/// - `amount` - Optional price at which buyer purchased at, or the maximum price the buyer
+		///   pays for a Dutch auction listing
+		/// - `recipient` - Optional account or NFT receiving the RMRK NFT instead of the buyer, an
+		///   NFT of another account has to accept it
+		#[pallet::weight(
+			10_000 + T::DbWeight::get().reads_writes(1, 1) + Pallet::<T>::nft_change_weight()
+		)]
 		#[transactional]
 		pub fn buy(
 			origin: OriginFor<T>,
 			collection_id: CollectionId,
 			nft_id: NftId,
 			amount: Option<BalanceOf<T>>,
+			recipient: Option<AccountIdOrCollectionNftTuple<T::AccountId>>,
 		) -> DispatchResult {
 			let sender = ensure_signed(origin)?;
 
-			Self::do_buy(sender, collection_id, nft_id, amount, false)
+			Self::do_buy(sender, collection_id, nft_id, amount, None, recipient)
 		}
 
 		/// List a RMRK NFT on the Marketplace for purchase. A listing can be cancelled, and is
 		/// automatically considered cancelled when a `buy` is executed on top of a given listing.
-		/// An NFT that has another NFT as its owner CANNOT be listed. An NFT owned by a NFT must
-		/// first be sent to an account before being listed.
+		/// An NFT owned by another NFT can be listed by its root owner, it is detached from its
+		/// parent and unequipped when bought. The NFT is locked until the listing is cancelled,
+		/// bought or expires, so it cannot be sent or burned in the meantime.
 		///
 		/// Parameters:
 		/// 	- `origin` - Account of owner of the RMRK NFT to be listed
 		/// 	- `collection_id` - Collection id of the RMRK NFT
 		/// 	- `nft_id` - NFT id of the RMRK NFT
 		/// - `amount` - Price of the RMRK NFT
+		/// - `expires` - Optional block after which the listing can't be bought
+		/// - `asset_id` - Asset the price is in, the native currency if `None`
 		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1))]
 		#[transactional]
 		pub fn list(
@@ -226,75 +833,456 @@ pub mod pallet {
 			nft_id: NftId,
 			amount: BalanceOf<T>,
 			expires: Option<T::BlockNumber>,
+			asset_id: Option<T::AssetId>,
 		) -> DispatchResult {
 			let sender = ensure_signed(origin)?;
-			let owner = pallet_uniques::Pallet::<T>::owner(collection_id, nft_id)
-				.ok_or(Error::<T>::TokenDoesNotExist)?;
-
-			// Ensure that the NFT is not owned by an NFT
-			ensure!(
-				!Self::is_nft_owned_by_nft(collection_id, nft_id),
-				Error::<T>::CannotListNftOwnedByNft
-			);
-			// Ensure sender is the owner
+			Self::ensure_trading(collection_id)?;
+			let owner = Self::root_owner(collection_id, nft_id)?;
+			// Ensure sender is the root owner
 			ensure!(sender == owner, Error::<T>::NoPermission);
-			// TODO: Lock NFT to prevent transfers or interactions with the NFT
 
-			// Check if a prior listing is in storage from previous owner and update if found
-			if Self::is_nft_listed(collection_id, nft_id) {
-				ListedNfts::<T>::remove(collection_id, nft_id);
-			}
+			// Replace any prior listing or bundle of the NFT
+			Self::clear_listing(collection_id, nft_id)?;
+
+			// Lock NFT to prevent transfers or interactions with the NFT while listed
+			pallet_rmrk_core::Pallet::<T>::lock_nft(
+				collection_id,
+				nft_id,
+				LockReason::Listed,
+				expires,
+			)?;
 
 			// Add new ListInfo with listed_by, amount, Option<BlockNumber>
 			ListedNfts::<T>::insert(
 				collection_id,
 				nft_id,
-				ListInfo { listed_by: sender, amount, expires },
+				ListInfo {
+					listed_by: sender,
+					amount,
+					asset_id,
+					expires,
+					dutch: None,
+					buyers: None,
+				},
 			);
 
-			Self::deposit_event(Event::TokenListed { owner, collection_id, nft_id, price: amount });
+			Self::deposit_event(Event::TokenListed {
+				owner,
+				collection_id,
+				nft_id,
+				price: amount,
+				asset_id,
+			});
 
 			Ok(())
 		}
 
-		/// Unlist a RMRK NFT on the Marketplace and remove from storage in `Listings`.
+		/// List a RMRK NFT on the Marketplace as a Dutch auction. The price declines from the
+		/// start price at block `start` to the floor price at block `end`, either every block or
+		/// every `step` blocks, and stays at the floor price afterwards. A purchase is charged the
+		/// price at the block of the purchase.
 		///
 		/// Parameters:
-		/// - `origin` - Account owner of the listed RMRK NFT
+		/// - `origin` - Account of owner of the RMRK NFT to be listed
 		/// - `collection_id` - Collection id of the RMRK NFT
 		/// - `nft_id` - NFT id of the RMRK NFT
+		/// - `start_price` - Price until block `start`
+		/// - `floor_price` - Price from block `end` on
+		/// - `start` - Block at which the price starts to decline
+		/// - `end` - Block at which the price reaches the floor price
+		/// - `step` - Number of blocks between price drops, zero for a drop every block
+		/// - `expires` - Optional block after which the listing can't be bought
+		/// - `asset_id` - Asset the price is in, the native currency if `None`
 		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1))]
 		#[transactional]
-		pub fn unlist(
+		pub fn list_dutch(
 			origin: OriginFor<T>,
 			collection_id: CollectionId,
 			nft_id: NftId,
+			start_price: BalanceOf<T>,
+			floor_price: BalanceOf<T>,
+			start: T::BlockNumber,
+			end: T::BlockNumber,
+			step: T::BlockNumber,
+			expires: Option<T::BlockNumber>,
+			asset_id: Option<T::AssetId>,
 		) -> DispatchResult {
 			let sender = ensure_signed(origin)?;
-			// Check if NFT is still in ListedNfts storage
-			ensure!(Self::is_nft_listed(collection_id, nft_id), Error::<T>::CannotUnlistToken);
-			let owner = pallet_uniques::Pallet::<T>::owner(collection_id, nft_id)
-				.ok_or(Error::<T>::TokenDoesNotExist)?;
-			// Ensure owner of NFT is performing call to unlist
+			Self::ensure_trading(collection_id)?;
+			let owner = Self::root_owner(collection_id, nft_id)?;
+			// Ensure sender is the root owner
 			ensure!(sender == owner, Error::<T>::NoPermission);
-			// TODO: Set the NFT lock to flase to allow interactions with the NFT
-			// Remove from storage
-			ListedNfts::<T>::remove(collection_id, nft_id);
-			// Emit TokenUnlisted Event
-			Self::deposit_event(Event::TokenUnlisted { owner, collection_id, nft_id });
+			ensure!(end > start && floor_price <= start_price, Error::<T>::InvalidDutchAuction);
 
-			Ok(())
-		}
+			// Replace any prior listing or bundle of the NFT
+			Self::clear_listing(collection_id, nft_id)?;
 
-		/// Make an offer on a RMRK NFT for purchase. An offer can be set with an expiration where
-		/// the offer can no longer be accepted by the RMRK NFT owner
-		///
-		/// Parameters:
+			// Lock NFT to prevent transfers or interactions with the NFT while listed
+			pallet_rmrk_core::Pallet::<T>::lock_nft(
+				collection_id,
+				nft_id,
+				LockReason::Listed,
+				expires,
+			)?;
+
+			ListedNfts::<T>::insert(
+				collection_id,
+				nft_id,
+				ListInfo {
+					listed_by: sender,
+					amount: start_price,
+					asset_id,
+					expires,
+					dutch: Some(DutchAuction { floor_price, start, end, step }),
+					buyers: None,
+				},
+			);
+
+			Self::deposit_event(Event::TokenListedDutch {
+				owner,
+				collection_id,
+				nft_id,
+				start_price,
+				floor_price,
+				start,
+				end,
+				asset_id,
+			});
+
+			Ok(())
+		}
+
+		/// List a RMRK NFT on the Marketplace for purchase by a set of buyers only, e.g. for an
+		/// over-the-counter deal at a price set by the owner. Anyone else trying to buy the NFT
+		/// is rejected. Offers can still be made and accepted as for a public listing.
+		///
+		/// Parameters:
+		/// - `origin` - Account of owner of the RMRK NFT to be listed
+		/// - `collection_id` - Collection id of the RMRK NFT
+		/// - `nft_id` - NFT id of the RMRK NFT
+		/// - `amount` - Price of the RMRK NFT
+		/// - `expires` - Optional block after which the listing can't be bought
+		/// - `asset_id` - Asset the price is in, the native currency if `None`
+		/// - `buyers` - Accounts allowed to buy the RMRK NFT, at most `MaxReservedBuyers`
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1))]
+		#[transactional]
+		pub fn list_private(
+			origin: OriginFor<T>,
+			collection_id: CollectionId,
+			nft_id: NftId,
+			amount: BalanceOf<T>,
+			expires: Option<T::BlockNumber>,
+			asset_id: Option<T::AssetId>,
+			buyers: Vec<T::AccountId>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Self::ensure_trading(collection_id)?;
+			let owner = Self::root_owner(collection_id, nft_id)?;
+			// Ensure sender is the root owner
+			ensure!(sender == owner, Error::<T>::NoPermission);
+			ensure!(!buyers.is_empty(), Error::<T>::NoReservedBuyers);
+			let bounded_buyers: BoundedVec<T::AccountId, T::MaxReservedBuyers> =
+				buyers.clone().try_into().map_err(|_| Error::<T>::TooManyReservedBuyers)?;
+
+			// Replace any prior listing or bundle of the NFT
+			Self::clear_listing(collection_id, nft_id)?;
+
+			// Lock NFT to prevent transfers or interactions with the NFT while listed
+			pallet_rmrk_core::Pallet::<T>::lock_nft(
+				collection_id,
+				nft_id,
+				LockReason::Listed,
+				expires,
+			)?;
+
+			ListedNfts::<T>::insert(
+				collection_id,
+				nft_id,
+				ListInfo {
+					listed_by: sender,
+					amount,
+					asset_id,
+					expires,
+					dutch: None,
+					buyers: Some(bounded_buyers),
+				},
+			);
+
+			Self::deposit_event(Event::TokenListedPrivately {
+				owner,
+				collection_id,
+				nft_id,
+				price: amount,
+				asset_id,
+				buyers,
+			});
+
+			Ok(())
+		}
+
+		/// List a RMRK NFT on the Marketplace for purchase, sending it to the NFT escrow account
+		/// of the pallet until it is sold or unlisted. Unlike `list`, the NFT cannot change owner
+		/// while listed. An escrowed NFT stays in `EscrowedNfts` under the seller, and `unlist`
+		/// or the expiry of the listing returns it to the seller.
+		///
+		/// Parameters:
+		/// - `origin` - Account of owner of the RMRK NFT to be listed
+		/// - `collection_id` - Collection id of the RMRK NFT
+		/// - `nft_id` - NFT id of the RMRK NFT
+		/// - `amount` - Price of the RMRK NFT
+		/// - `expires` - Optional block after which the listing can't be bought
+		/// - `asset_id` - Asset the price is in, the native currency if `None`
+		#[pallet::weight(
+			10_000 + T::DbWeight::get().reads_writes(2, 3) + Pallet::<T>::nft_change_weight()
+		)]
+		#[transactional]
+		pub fn list_escrowed(
+			origin: OriginFor<T>,
+			collection_id: CollectionId,
+			nft_id: NftId,
+			amount: BalanceOf<T>,
+			expires: Option<T::BlockNumber>,
+			asset_id: Option<T::AssetId>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin.clone())?;
+			Self::ensure_trading(collection_id)?;
+			let owner = Self::root_owner(collection_id, nft_id)?;
+			// Ensure sender is the root owner
+			ensure!(sender == owner, Error::<T>::NoPermission);
+
+			// Replace any prior listing or bundle of the NFT
+			Self::clear_listing(collection_id, nft_id)?;
+
+			// Hold the NFT in escrow, so it cannot be sent or burned while listed
+			let escrow = AccountIdOrCollectionNftTuple::AccountId(Self::nft_escrow_account());
+			Self::escrow_send(origin, collection_id, nft_id, escrow)?;
+			EscrowedNfts::<T>::insert(&sender, (collection_id, nft_id), ());
+
+			ListedNfts::<T>::insert(
+				collection_id,
+				nft_id,
+				ListInfo {
+					listed_by: sender,
+					amount,
+					asset_id,
+					expires,
+					dutch: None,
+					buyers: None,
+				},
+			);
+
+			Self::deposit_event(Event::TokenListedEscrowed {
+				owner,
+				collection_id,
+				nft_id,
+				price: amount,
+				asset_id,
+			});
+
+			Ok(())
+		}
+
+		/// Unlist a RMRK NFT on the Marketplace and remove from storage in `Listings`.
+		///
+		/// Parameters:
+		/// - `origin` - Account owner of the listed RMRK NFT
+		/// - `collection_id` - Collection id of the RMRK NFT
+		/// - `nft_id` - NFT id of the RMRK NFT
+		#[pallet::weight(
+			10_000 + T::DbWeight::get().reads_writes(1, 1) + Pallet::<T>::nft_change_weight()
+		)]
+		#[transactional]
+		pub fn unlist(
+			origin: OriginFor<T>,
+			collection_id: CollectionId,
+			nft_id: NftId,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			// Check if NFT is still in ListedNfts storage
+			ensure!(Self::is_nft_listed(collection_id, nft_id), Error::<T>::CannotUnlistToken);
+			let owner = Self::seller_of(collection_id, nft_id)?;
+			// Ensure owner of NFT is performing call to unlist
+			ensure!(sender == owner, Error::<T>::NoPermission);
+			// Return an escrowed NFT to the owner before its listing is removed
+			Self::release_escrow(&owner, collection_id, nft_id)?;
+			// Remove from storage
+			ListedNfts::<T>::remove(collection_id, nft_id);
+			// Release the NFT lock to allow interactions with the NFT
+			pallet_rmrk_core::Pallet::<T>::unlock_nft(collection_id, nft_id, LockReason::Listed)?;
+			// Emit TokenUnlisted Event
+			Self::deposit_event(Event::TokenUnlisted { owner, collection_id, nft_id });
+
+			Ok(())
+		}
+
+		/// List several RMRK NFTs of the sender for a single price. Buying the bundle sends all
+		/// its NFTs at once. The bundle is invalidated if any of its NFTs is sent, burned or
+		/// listed separately. Like `list`, nested NFTs can be bundled by their root owner, but
+		/// not together with an NFT they are nested in.
+		///
+		/// Parameters:
+		/// - `origin` - Account of owner of the RMRK NFTs to be listed
+		/// - `items` - Collection and NFT ids of the RMRK NFTs, at most `MaxBundleSize`
+		/// - `amount` - Price of the bundle
+		/// - `expires` - Optional block after which the bundle can't be bought
+		/// - `asset_id` - Asset the price is in, the native currency if `None`
+		#[pallet::weight(
+			10_000 + T::DbWeight::get().reads_writes(3, 3).saturating_mul(items.len() as Weight)
+		)]
+		#[transactional]
+		pub fn list_bundle(
+			origin: OriginFor<T>,
+			items: Vec<(CollectionId, NftId)>,
+			amount: BalanceOf<T>,
+			expires: Option<T::BlockNumber>,
+			asset_id: Option<T::AssetId>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let items: BoundedVec<(CollectionId, NftId), T::MaxBundleSize> =
+				items.try_into().map_err(|_| Error::<T>::BundleTooLarge)?;
+			ensure!(!items.is_empty(), Error::<T>::EmptyBundle);
+
+			let bundle_id = Self::get_next_bundle_id()?;
+			for (index, &(collection_id, nft_id)) in items.iter().enumerate() {
+				Self::ensure_trading(collection_id)?;
+				ensure!(
+					!items[..index].contains(&(collection_id, nft_id)),
+					Error::<T>::DuplicateBundleItem
+				);
+				let owner = Self::root_owner(collection_id, nft_id)?;
+				// Ensure sender is the root owner
+				ensure!(sender == owner, Error::<T>::NoPermission);
+				// A nested NFT is sold along with the NFT it is nested in, so they cannot be
+				// bundled together
+				ensure!(
+					!Self::is_nested_with(collection_id, nft_id, &items[..index]),
+					Error::<T>::NestedBundleItem
+				);
+
+				// The NFT is only sold in the new bundle
+				Self::clear_listing(collection_id, nft_id)?;
+
+				pallet_rmrk_core::Pallet::<T>::lock_nft(
+					collection_id,
+					nft_id,
+					LockReason::Listed,
+					expires,
+				)?;
+				BundledNfts::<T>::insert(collection_id, nft_id, bundle_id);
+			}
+
+			Bundles::<T>::insert(
+				bundle_id,
+				BundleInfo { listed_by: sender.clone(), items, amount, asset_id, expires },
+			);
+
+			Self::deposit_event(Event::BundleListed {
+				owner: sender,
+				bundle_id,
+				price: amount,
+				asset_id,
+			});
+
+			Ok(())
+		}
+
+		/// Unlist a bundle of RMRK NFTs
+		///
+		/// Parameters:
+		/// - `origin` - Account that listed the bundle
+		/// - `bundle_id` - Id of the bundle
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1))]
+		#[transactional]
+		pub fn unlist_bundle(origin: OriginFor<T>, bundle_id: BundleId) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let bundle = Bundles::<T>::get(bundle_id).ok_or(Error::<T>::UnknownBundle)?;
+			ensure!(sender == bundle.listed_by, Error::<T>::NoPermission);
+
+			Self::remove_bundle(bundle_id);
+
+			Ok(())
+		}
+
+		/// Buy a bundle of RMRK NFTs. The NFTs are sent to the buyer in a single transaction.
+		///
+		/// Parameters:
+		/// - `origin` - Account of the potential buyer
+		/// - `bundle_id` - Id of the bundle
+		/// - `amount` - Optional price at which buyer purchased at
+		#[pallet::weight(
+			10_000 +
+				T::DbWeight::get()
+					.reads_writes(8, 8)
+					.saturating_add(Pallet::<T>::nft_change_weight())
+					.saturating_mul(T::MaxBundleSize::get() as Weight)
+		)]
+		#[transactional]
+		pub fn buy_bundle(
+			origin: OriginFor<T>,
+			bundle_id: BundleId,
+			amount: Option<BalanceOf<T>>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let bundle = Bundles::<T>::take(bundle_id).ok_or(Error::<T>::UnknownBundle)?;
+			ensure!(sender != bundle.listed_by, Error::<T>::CannotBuyOwnToken);
+			if let Some(expires) = bundle.expires {
+				ensure!(
+					expires > <frame_system::Pallet<T>>::block_number(),
+					Error::<T>::ListingHasExpired
+				);
+			}
+			// Check if the price is equal to amount to prevent front running a buy
+			if let Some(amount) = amount {
+				ensure!(bundle.amount == amount, Error::<T>::PriceDiffersFromExpected);
+			}
+
+			// The price is split evenly between the NFTs for the fee and royalties, the first NFT
+			// also takes the remainder
+			let count: BalanceOf<T> = (bundle.items.len() as u32).into();
+			let share = bundle.amount / count;
+			let mut remainder = bundle.amount % count;
+			for (collection_id, nft_id) in bundle.items {
+				Self::ensure_trading(collection_id)?;
+				BundledNfts::<T>::remove(collection_id, nft_id);
+				ensure!(
+					Self::root_owner(collection_id, nft_id).ok().as_ref() ==
+						Some(&bundle.listed_by),
+					Error::<T>::TokenNotForSale
+				);
+				Self::do_sale(
+					bundle.listed_by.clone(),
+					sender.clone(),
+					collection_id,
+					nft_id,
+					share.saturating_add(remainder),
+					bundle.asset_id,
+				)?;
+				remainder = Zero::zero();
+			}
+
+			Self::deposit_event(Event::BundleSold {
+				owner: bundle.listed_by,
+				buyer: sender,
+				bundle_id,
+				price: bundle.amount,
+			});
+
+			Ok(())
+		}
+
+		/// Make an offer on a RMRK NFT for purchase. An offer can be set with an expiration where
+		/// the offer can no longer be accepted by the RMRK NFT owner. At most `MaxOffersPerNft`
+		/// offers can be made on a RMRK NFT at a time.
+		///
+		/// Parameters:
 		/// - `origin` - Account of the potential buyer
 		/// - `collection_id` - Collection id of the RMRK NFT
 		/// - `nft_id` - NFT id of the RMRK NFT
 		/// - `amount` - Price of the RMRK NFT
 		/// - `expiration` - Expiration of the offer
+		/// - `asset_id` - Asset the amount is in, the native currency if `None`
+		/// - `recipient` - Optional NFT of the sender receiving the RMRK NFT instead of the
+		///   sender's account once the offer is accepted
 		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1))]
 		#[transactional]
 		pub fn make_offer(
@@ -303,13 +1291,15 @@ pub mod pallet {
 			nft_id: NftId,
 			amount: BalanceOf<T>,
 			expires: Option<T::BlockNumber>,
+			asset_id: Option<T::AssetId>,
+			recipient: Option<AccountIdOrCollectionNftTuple<T::AccountId>>,
 		) -> DispatchResult {
 			let sender = ensure_signed(origin)?;
-			// Ensure amount is above the minimum threshold
-			ensure!(amount >= T::MinimumOfferAmount::get(), Error::<T>::OfferTooLow);
-			// Ensure NFT exists & sender is not owner
-			let owner = pallet_uniques::Pallet::<T>::owner(collection_id, nft_id)
-				.ok_or(Error::<T>::TokenDoesNotExist)?;
+			Self::ensure_trading(collection_id)?;
+			// Ensure amount is above the minimum threshold of the currency or asset
+			ensure!(Self::is_offer_amount_valid(asset_id, amount), Error::<T>::OfferTooLow);
+			// Ensure NFT exists & sender is not the root owner, or the seller of an escrowed NFT
+			let owner = Self::seller_of(collection_id, nft_id)?;
 
 			ensure!(sender != owner, Error::<T>::CannotOfferOnOwnToken);
 			// If offer has already been made, must withdraw_offer first before making a new offer
@@ -317,16 +1307,23 @@ pub mod pallet {
 				!Self::has_active_offer(collection_id, nft_id, sender.clone()),
 				Error::<T>::AlreadyOffered
 			);
+			Self::ensure_offer_recipient(&sender, &recipient)?;
+
+			let token_id = (collection_id, nft_id);
+			OffersCount::<T>::try_mutate(token_id, |count| -> DispatchResult {
+				ensure!(*count < T::MaxOffersPerNft::get(), Error::<T>::TooManyOffers);
+				*count += 1;
+				Ok(())
+			})?;
 
 			// Reserve currency from offerer account
-			<T as pallet::Config>::Currency::reserve(&sender, amount)?;
+			Self::reserve_funds(asset_id, &sender, amount)?;
 
-			let token_id = (collection_id, nft_id);
 			// Insert new offer into Offers storage
 			Offers::<T>::insert(
 				token_id,
 				sender.clone(),
-				Offer { maker: sender.clone(), amount, expires },
+				Offer { maker: sender.clone(), amount, asset_id, expires, recipient },
 			);
 
 			// Emit OfferPlaced event
@@ -335,6 +1332,80 @@ pub mod pallet {
 				collection_id,
 				nft_id,
 				price: amount,
+				asset_id,
+			});
+
+			Ok(())
+		}
+
+		/// Raise or lower an offer on a RMRK NFT in place, reserving or unreserving the difference
+		/// to the previous amount
+		///
+		/// Parameters:
+		/// - `origin` - Account that made the offer
+		/// - `collection_id` - Collection id of the RMRK NFT
+		/// - `nft_id` - NFT id of the RMRK NFT
+		/// - `amount` - New price offered for the RMRK NFT
+		/// - `expires` - New expiration of the offer, `None` to keep the current expiration
+		/// - `recipient` - New recipient of the RMRK NFT, `None` to keep the current recipient
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2,2))]
+		#[transactional]
+		pub fn update_offer(
+			origin: OriginFor<T>,
+			collection_id: CollectionId,
+			nft_id: NftId,
+			amount: BalanceOf<T>,
+			expires: Option<Option<T::BlockNumber>>,
+			recipient: Option<Option<AccountIdOrCollectionNftTuple<T::AccountId>>>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Self::ensure_trading(collection_id)?;
+			if let Some(recipient) = &recipient {
+				Self::ensure_offer_recipient(&sender, recipient)?;
+			}
+
+			let token_id = (collection_id, nft_id);
+			let offer = Offers::<T>::try_mutate(
+				token_id,
+				sender.clone(),
+				|maybe_offer| -> Result<OfferOf<T>, DispatchError> {
+					let offer = maybe_offer.as_mut().ok_or(Error::<T>::UnknownOffer)?;
+					// An expired offer is about to be refunded
+					if let Some(expires) = offer.expires {
+						ensure!(
+							expires > <frame_system::Pallet<T>>::block_number(),
+							Error::<T>::OfferHasExpired
+						);
+					}
+					// Ensure amount is above the minimum threshold of the currency or asset
+					ensure!(
+						Self::is_offer_amount_valid(offer.asset_id, amount),
+						Error::<T>::OfferTooLow
+					);
+
+					// Reserve or unreserve the difference to the previous amount
+					if amount > offer.amount {
+						Self::reserve_funds(offer.asset_id, &sender, amount - offer.amount)?;
+					} else {
+						Self::unreserve_funds(offer.asset_id, &sender, offer.amount - amount)?;
+					}
+					offer.amount = amount;
+					if let Some(expires) = expires {
+						offer.expires = expires;
+					}
+					if let Some(recipient) = recipient {
+						offer.recipient = recipient;
+					}
+					Ok(offer.clone())
+				},
+			)?;
+
+			Self::deposit_event(Event::OfferUpdated {
+				offerer: sender,
+				collection_id,
+				nft_id,
+				price: offer.amount,
+				expires: offer.expires,
 			});
 
 			Ok(())
@@ -363,6 +1434,7 @@ pub mod pallet {
 				sender.clone(),
 				|maybe_offer| -> DispatchResult {
 					let offer = maybe_offer.take().ok_or(Error::<T>::UnknownOffer)?;
+					Self::decrement_offers_count(token_id);
 					// Ensure NFT exists & sender is not owner
 					let owner = pallet_uniques::Pallet::<T>::owner(collection_id, nft_id)
 						.ok_or(Error::<T>::TokenDoesNotExist)?;
@@ -373,7 +1445,7 @@ pub mod pallet {
 					);
 
 					// Unreserve currency from offerer account
-					<T as pallet::Config>::Currency::unreserve(&offer.maker, offer.amount);
+					Self::unreserve_funds(offer.asset_id, &offer.maker, offer.amount)?;
 					// Emit OfferWithdrawn Event
 					Self::deposit_event(Event::OfferWithdrawn { sender, collection_id, nft_id });
 
@@ -388,8 +1460,11 @@ pub mod pallet {
 		// - `origin` - Account of the current owner that is accepting the offerer's offer
 		// - `collection_id` - Collection id of the RMRK NFT
 		// - `nft_id` - NFT id of the RMRK NFT
-		// - `offerer` - Account that made the offer
-		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1))]
+		// - `offerer` - Account that made the offer, the RMRK NFT is delivered to the recipient of
+		//   the offer if set
+		#[pallet::weight(
+			10_000 + T::DbWeight::get().reads_writes(1, 1) + Pallet::<T>::nft_change_weight()
+		)]
 		#[transactional]
 		pub fn accept_offer(
 			origin: OriginFor<T>,
@@ -399,114 +1474,1273 @@ pub mod pallet {
 		) -> DispatchResult {
 			let sender = ensure_signed(origin)?;
 			// Ensure NFT exists & sender is not owner
-			let owner = pallet_uniques::Pallet::<T>::owner(collection_id, nft_id)
-				.ok_or(Error::<T>::TokenDoesNotExist)?;
+			let owner = Self::seller_of(collection_id, nft_id)?;
 			// Cannot accept offer if not the owner
 			ensure!(sender == owner, Error::<T>::NoPermission);
 
 			let token_id = (collection_id, nft_id);
-			Offers::<T>::try_mutate_exists(
-				token_id,
-				offerer.clone(),
-				|maybe_offer| -> DispatchResult {
-					let offer = maybe_offer.take().ok_or(Error::<T>::UnknownOffer)?;
+			// The other offers are refunded once the NFT is sent
+			let offer = Offers::<T>::take(token_id, &offerer).ok_or(Error::<T>::UnknownOffer)?;
+			Self::decrement_offers_count(token_id);
 
-					if let Some(expires) = offer.expires {
-						if expires <= <frame_system::Pallet<T>>::block_number() {
-							return Err(Error::<T>::OfferHasExpired.into())
-						}
-					}
+			if let Some(expires) = offer.expires {
+				if expires <= <frame_system::Pallet<T>>::block_number() {
+					return Err(Error::<T>::OfferHasExpired.into())
+				}
+			}
 
-					<T as pallet::Config>::Currency::unreserve(&offer.maker, offer.amount);
-					Self::do_buy(offer.maker, collection_id, nft_id, None, true)?;
-					// Emit OfferAccepted event
-					Self::deposit_event(Event::OfferAccepted {
-						owner,
-						buyer: offerer,
-						collection_id,
-						nft_id,
-					});
+			Self::unreserve_funds(offer.asset_id, &offer.maker, offer.amount)?;
+			let recipient = offer.recipient.clone();
+			Self::do_buy(offer.maker.clone(), collection_id, nft_id, None, Some(offer), recipient)?;
+			// Emit OfferAccepted event
+			Self::deposit_event(Event::OfferAccepted {
+				owner,
+				buyer: offerer,
+				collection_id,
+				nft_id,
+			});
 
-					Ok(())
-				},
-			)
+			Ok(())
 		}
-	}
-}
-
-impl<T: Config> Pallet<T>
-where
-	T: pallet_uniques::Config<ClassId = CollectionId, InstanceId = NftId>,
-{
-	/// Buy the NFT helper funciton logic to handle both transactional calls of `buy` and
-	/// `accept_offer`
-	///
-	/// Parameters:
-	/// - `buyer`: The account that is buying the RMRK NFT
-	/// - `collection_id`: The collection id of the RMRK NFT
-	/// - `nft_id`: The id of the RMRK NFT
-	/// - `amount`: Optional amount at which the buyer purchased a RMRK NFT
-	/// - `is_offer`: Whether the call is from `accept_offer` or `buy`
-	fn do_buy(
-		buyer: T::AccountId,
-		collection_id: CollectionId,
-		nft_id: NftId,
-		amount: Option<BalanceOf<T>>,
-		is_offer: bool,
-	) -> DispatchResult {
-		// Ensure buyer is not the root owner
-		let owner = pallet_uniques::Pallet::<T>::owner(collection_id, nft_id)
-			.ok_or(Error::<T>::TokenDoesNotExist)?;
-		ensure!(buyer != owner, Error::<T>::CannotBuyOwnToken);
-
-		let owner_origin = T::Origin::from(RawOrigin::Signed(owner.clone()));
-		let token_id = (collection_id, nft_id);
 
-		let list_price = if is_offer {
-			Offers::<T>::get(token_id, buyer.clone())
-				.map(|o| o.amount)
-				.ok_or(Error::<T>::UnknownOffer)?
-		} else {
-			let list_info =
-				ListedNfts::<T>::take(collection_id, nft_id).ok_or(Error::<T>::TokenNotForSale)?;
-			// Ensure that the current owner is the one that listed the NFT
-			ensure!(list_info.listed_by == owner, Error::<T>::TokenNotForSale);
-			// Ensure the listing has not expired if Some(expires)
-			// if None then there is no expiration
-			if let Some(expires) = list_info.expires {
+		/// Make an offer on any RMRK NFT of a collection, optionally only on NFTs with a given
+		/// property. Any owner of a matching NFT can accept the offer
+		///
+		/// Parameters:
+		/// - `origin` - Account of the potential buyer
+		/// - `collection_id` - Collection id of the RMRK NFTs
+		/// - `amount` - Price offered for a RMRK NFT
+		/// - `expires` - Expiration of the offer
+		/// - `property` - Optional property key and value the RMRK NFT must have
+		/// - `asset_id` - Asset the amount is in, the native currency if `None`
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2,1))]
+		#[transactional]
+		pub fn make_collection_offer(
+			origin: OriginFor<T>,
+			collection_id: CollectionId,
+			amount: BalanceOf<T>,
+			expires: Option<T::BlockNumber>,
+			property: Option<(KeyLimitOf<T>, ValueLimitOf<T>)>,
+			asset_id: Option<T::AssetId>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Self::ensure_trading(collection_id)?;
+			// Ensure amount is above the minimum threshold of the currency or asset
+			ensure!(Self::is_offer_amount_valid(asset_id, amount), Error::<T>::OfferTooLow);
+			ensure!(
+				pallet_rmrk_core::Collections::<T>::contains_key(collection_id),
+				Error::<T>::CollectionDoesNotExist
+			);
+			// Must withdraw the collection offer first before making a new one
+			ensure!(
+				!CollectionOffers::<T>::contains_key(collection_id, &sender),
+				Error::<T>::AlreadyOffered
+			);
+
+			// Reserve currency from offerer account
+			Self::reserve_funds(asset_id, &sender, amount)?;
+
+			CollectionOffers::<T>::insert(
+				collection_id,
+				sender.clone(),
+				CollectionOffer { maker: sender.clone(), amount, asset_id, expires, property },
+			);
+
+			Self::deposit_event(Event::CollectionOfferPlaced {
+				offerer: sender,
+				collection_id,
+				price: amount,
+				asset_id,
+			});
+
+			Ok(())
+		}
+
+		/// Withdraw an offer on any RMRK NFT of a collection
+		///
+		/// Parameters:
+		/// - `origin` - Account that wants to withdraw their offer
+		/// - `collection_id` - Collection id of the RMRK NFTs
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1))]
+		#[transactional]
+		pub fn withdraw_collection_offer(
+			origin: OriginFor<T>,
+			collection_id: CollectionId,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let offer = CollectionOffers::<T>::take(collection_id, &sender)
+				.ok_or(Error::<T>::UnknownOffer)?;
+			// Unreserve currency from offerer account
+			Self::unreserve_funds(offer.asset_id, &offer.maker, offer.amount)?;
+
+			Self::deposit_event(Event::CollectionOfferWithdrawn { offerer: sender, collection_id });
+
+			Ok(())
+		}
+
+		/// Accept an offer on any RMRK NFT of a collection for one of the sender's NFTs
+		///
+		/// Parameters:
+		/// - `origin` - Account of the current owner that is accepting the offerer's offer
+		/// - `collection_id` - Collection id of the RMRK NFT
+		/// - `nft_id` - NFT id of the RMRK NFT
+		/// - `offerer` - Account that made the collection offer
+		#[pallet::weight(
+			10_000 + T::DbWeight::get().reads_writes(2, 1) + Pallet::<T>::nft_change_weight()
+		)]
+		#[transactional]
+		pub fn accept_collection_offer(
+			origin: OriginFor<T>,
+			collection_id: CollectionId,
+			nft_id: NftId,
+			offerer: T::AccountId,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let owner = Self::seller_of(collection_id, nft_id)?;
+			// Cannot accept offer if not the owner
+			ensure!(sender == owner, Error::<T>::NoPermission);
+
+			// The offer covers a single NFT
+			let offer = CollectionOffers::<T>::take(collection_id, &offerer)
+				.ok_or(Error::<T>::UnknownOffer)?;
+
+			if let Some(expires) = offer.expires {
+				ensure!(
+					expires > <frame_system::Pallet<T>>::block_number(),
+					Error::<T>::OfferHasExpired
+				);
+			}
+			if let Some((key, value)) = offer.property {
+				ensure!(
+					pallet_rmrk_core::Pallet::<T>::properties((collection_id, Some(nft_id), key)) ==
+						Some(value),
+					Error::<T>::PropertyMismatch
+				);
+			}
+
+			Self::unreserve_funds(offer.asset_id, &offer.maker, offer.amount)?;
+			Self::do_buy(
+				offer.maker.clone(),
+				collection_id,
+				nft_id,
+				None,
+				Some(Offer {
+					maker: offer.maker,
+					amount: offer.amount,
+					asset_id: offer.asset_id,
+					expires: offer.expires,
+					recipient: None,
+				}),
+				None,
+			)?;
+
+			Self::deposit_event(Event::CollectionOfferAccepted {
+				owner,
+				buyer: offerer,
+				collection_id,
+				nft_id,
+			});
+
+			Ok(())
+		}
+
+		/// Propose to swap RMRK NFTs, optionally plus an amount, for RMRK NFTs of a counterparty.
+		/// The offered NFTs are locked and the amount reserved until the swap is accepted or
+		/// cancelled. Nested NFTs can be offered and requested, their root owner is the party,
+		/// but not along with an NFT they are nested in.
+		///
+		/// Parameters:
+		/// - `origin` - Account proposing the swap
+		/// - `counterparty` - Account that can accept the swap
+		/// - `offered` - Collection and NFT ids of the RMRK NFTs offered by the proposer
+		/// - `requested` - Collection and NFT ids of the RMRK NFTs requested from the counterparty
+		/// - `amount` - Amount paid by the proposer next to the offered NFTs, can be zero
+		/// - `asset_id` - Asset the amount is in, the native currency if `None`
+		#[pallet::weight(
+			10_000 +
+				T::DbWeight::get()
+					.reads_writes(3, 1)
+					.saturating_mul(offered.len().saturating_add(requested.len()) as Weight)
+		)]
+		#[transactional]
+		pub fn propose_swap(
+			origin: OriginFor<T>,
+			counterparty: T::AccountId,
+			offered: Vec<(CollectionId, NftId)>,
+			requested: Vec<(CollectionId, NftId)>,
+			amount: BalanceOf<T>,
+			asset_id: Option<T::AssetId>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			ensure!(sender != counterparty, Error::<T>::CannotSwapWithSelf);
+			let offered: BoundedVec<(CollectionId, NftId), T::MaxBundleSize> =
+				offered.try_into().map_err(|_| Error::<T>::SwapTooLarge)?;
+			let requested: BoundedVec<(CollectionId, NftId), T::MaxBundleSize> =
+				requested.try_into().map_err(|_| Error::<T>::SwapTooLarge)?;
+			ensure!(
+				!requested.is_empty() && (!offered.is_empty() || !amount.is_zero()),
+				Error::<T>::EmptySwap
+			);
+
+			for (index, &(collection_id, nft_id)) in offered.iter().enumerate() {
+				Self::ensure_trading(collection_id)?;
+				ensure!(
+					!offered[..index].contains(&(collection_id, nft_id)),
+					Error::<T>::DuplicateSwapItem
+				);
+				ensure!(
+					Self::root_owner(collection_id, nft_id)? == sender,
+					Error::<T>::NoPermission
+				);
+				// A nested NFT is sent along with the NFT it is nested in, so they cannot both be
+				// swapped
+				ensure!(
+					!Self::is_nested_with(collection_id, nft_id, &offered[..index]),
+					Error::<T>::NestedSwapItem
+				);
+				// Lock the NFT until the swap is accepted or cancelled
+				pallet_rmrk_core::Pallet::<T>::lock_nft(
+					collection_id,
+					nft_id,
+					LockReason::Swapped,
+					None,
+				)?;
+			}
+			for (index, &(collection_id, nft_id)) in requested.iter().enumerate() {
+				Self::ensure_trading(collection_id)?;
+				ensure!(
+					!requested[..index].contains(&(collection_id, nft_id)),
+					Error::<T>::DuplicateSwapItem
+				);
+				ensure!(
+					Self::root_owner(collection_id, nft_id)? == counterparty,
+					Error::<T>::NoPermission
+				);
+				ensure!(
+					!Self::is_nested_with(collection_id, nft_id, &requested[..index]),
+					Error::<T>::NestedSwapItem
+				);
+			}
+
+			if !amount.is_zero() {
+				Self::reserve_funds(asset_id, &sender, amount)?;
+			}
+
+			let swap_id = Self::get_next_swap_id()?;
+			Swaps::<T>::insert(
+				swap_id,
+				SwapInfo {
+					proposer: sender.clone(),
+					counterparty: counterparty.clone(),
+					offered,
+					requested,
+					amount,
+					asset_id,
+				},
+			);
+
+			Self::deposit_event(Event::SwapProposed { proposer: sender, counterparty, swap_id });
+
+			Ok(())
+		}
+
+		/// Accept a swap. The offered RMRK NFTs and amount go to the counterparty and the
+		/// requested RMRK NFTs to the proposer, in a single transaction.
+		///
+		/// Parameters:
+		/// - `origin` - Counterparty of the swap
+		/// - `swap_id` - Id of the swap
+		#[pallet::weight(
+			10_000 +
+				T::DbWeight::get()
+					.reads_writes(6, 6)
+					.saturating_add(Pallet::<T>::nft_change_weight())
+					.saturating_mul(T::MaxBundleSize::get().saturating_mul(2) as Weight)
+		)]
+		#[transactional]
+		pub fn accept_swap(origin: OriginFor<T>, swap_id: SwapId) -> DispatchResult {
+			let sender = ensure_signed(origin.clone())?;
+			let swap = Swaps::<T>::take(swap_id).ok_or(Error::<T>::UnknownSwap)?;
+			ensure!(sender == swap.counterparty, Error::<T>::NoPermission);
+
+			let proposer_origin = T::Origin::from(RawOrigin::Signed(swap.proposer.clone()));
+			for &(collection_id, nft_id) in swap.offered.iter() {
+				Self::ensure_trading(collection_id)?;
+				pallet_rmrk_core::Pallet::<T>::unlock_nft(
+					collection_id,
+					nft_id,
+					LockReason::Swapped,
+				)?;
+				// A nested NFT leaves its parent, so it cannot stay equipped
+				T::Equip::unequip_nft(collection_id, nft_id)?;
+				pallet_rmrk_core::Pallet::<T>::send(
+					proposer_origin.clone(),
+					collection_id,
+					nft_id,
+					AccountIdOrCollectionNftTuple::AccountId(sender.clone()),
+				)?;
+			}
+			for &(collection_id, nft_id) in swap.requested.iter() {
+				Self::ensure_trading(collection_id)?;
+				T::Equip::unequip_nft(collection_id, nft_id)?;
+				pallet_rmrk_core::Pallet::<T>::send(
+					origin.clone(),
+					collection_id,
+					nft_id,
+					AccountIdOrCollectionNftTuple::AccountId(swap.proposer.clone()),
+				)?;
+			}
+
+			if !swap.amount.is_zero() {
+				Self::unreserve_funds(swap.asset_id, &swap.proposer, swap.amount)?;
+				Self::transfer_funds(swap.asset_id, &swap.proposer, &sender, swap.amount)?;
+			}
+
+			Self::deposit_event(Event::SwapAccepted {
+				proposer: swap.proposer,
+				counterparty: sender,
+				swap_id,
+			});
+
+			Ok(())
+		}
+
+		/// Cancel a swap, releasing the offered RMRK NFTs and amount. The counterparty can
+		/// cancel a swap to reject it.
+		///
+		/// Parameters:
+		/// - `origin` - Proposer or counterparty of the swap
+		/// - `swap_id` - Id of the swap
+		#[pallet::weight(
+			10_000 +
+				T::DbWeight::get()
+					.reads_writes(1, 1)
+					.saturating_mul(T::MaxBundleSize::get() as Weight)
+		)]
+		#[transactional]
+		pub fn cancel_swap(origin: OriginFor<T>, swap_id: SwapId) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let swap = Swaps::<T>::take(swap_id).ok_or(Error::<T>::UnknownSwap)?;
+			ensure!(
+				sender == swap.proposer || sender == swap.counterparty,
+				Error::<T>::NoPermission
+			);
+
+			for (collection_id, nft_id) in swap.offered {
+				// Release the NFT, it may not exist anymore
+				let _ = pallet_rmrk_core::Pallet::<T>::unlock_nft(
+					collection_id,
+					nft_id,
+					LockReason::Swapped,
+				);
+			}
+			if !swap.amount.is_zero() {
+				Self::unreserve_funds(swap.asset_id, &swap.proposer, swap.amount)?;
+			}
+
+			Self::deposit_event(Event::SwapCancelled { swap_id });
+
+			Ok(())
+		}
+
+		/// Put a RMRK NFT up for an English auction. Bids must reach the reserve price and then
+		/// exceed the highest bid by the minimum increment. The NFT is locked during the auction
+		/// and sold to the highest bidder when the auction ends.
+		///
+		/// Parameters:
+		/// - `origin` - Account of owner of the RMRK NFT
+		/// - `collection_id` - Collection id of the RMRK NFT
+		/// - `nft_id` - NFT id of the RMRK NFT
+		/// - `reserve_price` - Minimum amount of the first bid
+		/// - `min_increment` - Minimum amount by which a bid must exceed the highest bid
+		/// - `duration` - Number of blocks the auction runs for
+		/// - `asset_id` - Asset the bids are in, the native currency if `None`
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3,3))]
+		#[transactional]
+		pub fn create_auction(
+			origin: OriginFor<T>,
+			collection_id: CollectionId,
+			nft_id: NftId,
+			reserve_price: BalanceOf<T>,
+			min_increment: BalanceOf<T>,
+			duration: T::BlockNumber,
+			asset_id: Option<T::AssetId>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Self::ensure_trading(collection_id)?;
+			let owner = pallet_uniques::Pallet::<T>::owner(collection_id, nft_id)
+				.ok_or(Error::<T>::TokenDoesNotExist)?;
+
+			// Ensure that the NFT is not owned by an NFT
+			ensure!(
+				!Self::is_nft_owned_by_nft(collection_id, nft_id),
+				Error::<T>::CannotListNftOwnedByNft
+			);
+			// Ensure sender is the owner
+			ensure!(sender == owner, Error::<T>::NoPermission);
+			ensure!(!duration.is_zero(), Error::<T>::InvalidAuctionDuration);
+
+			// Replace any prior listing or bundle of the NFT
+			Self::clear_listing(collection_id, nft_id)?;
+
+			// Lock NFT until the auction is settled
+			pallet_rmrk_core::Pallet::<T>::lock_nft(
+				collection_id,
+				nft_id,
+				LockReason::Listed,
+				None,
+			)?;
+
+			let end = <frame_system::Pallet<T>>::block_number().saturating_add(duration);
+			AuctionsEndingAt::<T>::try_append(end, (collection_id, nft_id))
+				.map_err(|_| Error::<T>::TooManyAuctionsEnding)?;
+			Auctions::<T>::insert(
+				collection_id,
+				nft_id,
+				AuctionInfo {
+					owner: owner.clone(),
+					reserve_price,
+					asset_id,
+					min_increment,
+					highest_bid: None,
+					end,
+				},
+			);
+
+			Self::deposit_event(Event::AuctionCreated {
+				owner,
+				collection_id,
+				nft_id,
+				reserve_price,
+				end,
+				asset_id,
+			});
+
+			Ok(())
+		}
+
+		/// Bid on an auctioned RMRK NFT. The bid amount is reserved and the previous highest
+		/// bidder is refunded. A bid close to the end of the auction extends the auction.
+		///
+		/// Parameters:
+		/// - `origin` - Account of the bidder
+		/// - `collection_id` - Collection id of the RMRK NFT
+		/// - `nft_id` - NFT id of the RMRK NFT
+		/// - `amount` - Bid amount
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(4,4))]
+		#[transactional]
+		pub fn bid(
+			origin: OriginFor<T>,
+			collection_id: CollectionId,
+			nft_id: NftId,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Self::ensure_trading(collection_id)?;
+			let now = <frame_system::Pallet<T>>::block_number();
+
+			Auctions::<T>::try_mutate(collection_id, nft_id, |maybe_auction| -> DispatchResult {
+				let auction = maybe_auction.as_mut().ok_or(Error::<T>::UnknownAuction)?;
+				ensure!(sender != auction.owner, Error::<T>::CannotBuyOwnToken);
+				ensure!(auction.end > now, Error::<T>::AuctionHasEnded);
+
+				// Ensure bid reaches the reserve price or outbids the highest bid
+				match &auction.highest_bid {
+					Some((_, highest)) => ensure!(
+						amount > *highest &&
+							amount >= highest.saturating_add(auction.min_increment),
+						Error::<T>::BidTooLow
+					),
+					None => ensure!(amount >= auction.reserve_price, Error::<T>::BidTooLow),
+				}
+
+				// Refund the outbid bidder and reserve the new bid
+				if let Some((bidder, highest)) = auction.highest_bid.take() {
+					Self::unreserve_funds(auction.asset_id, &bidder, highest)?;
+				}
+				Self::reserve_funds(auction.asset_id, &sender, amount)?;
+				auction.highest_bid = Some((sender.clone(), amount));
+
+				// Extend the auction if the bid was placed close to its end
+				let extension = T::AuctionExtensionPeriod::get();
+				if auction.end.saturating_sub(now) < extension {
+					let end = now.saturating_add(extension);
+					AuctionsEndingAt::<T>::mutate(auction.end, |ending| {
+						ending.retain(|token| *token != (collection_id, nft_id))
+					});
+					AuctionsEndingAt::<T>::try_append(end, (collection_id, nft_id))
+						.map_err(|_| Error::<T>::TooManyAuctionsEnding)?;
+					auction.end = end;
+					Self::deposit_event(Event::AuctionExtended { collection_id, nft_id, end });
+				}
+
+				Self::deposit_event(Event::BidPlaced {
+					bidder: sender,
+					collection_id,
+					nft_id,
+					amount,
+				});
+
+				Ok(())
+			})
+		}
+
+		/// Cancel an auction that has no bids yet.
+		///
+		/// Parameters:
+		/// - `origin` - Account of owner of the auctioned RMRK NFT
+		/// - `collection_id` - Collection id of the RMRK NFT
+		/// - `nft_id` - NFT id of the RMRK NFT
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3,3))]
+		#[transactional]
+		pub fn cancel_auction(
+			origin: OriginFor<T>,
+			collection_id: CollectionId,
+			nft_id: NftId,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let auction =
+				Auctions::<T>::get(collection_id, nft_id).ok_or(Error::<T>::UnknownAuction)?;
+			ensure!(sender == auction.owner, Error::<T>::NoPermission);
+			ensure!(auction.highest_bid.is_none(), Error::<T>::AuctionHasBids);
+
+			Auctions::<T>::remove(collection_id, nft_id);
+			AuctionsEndingAt::<T>::mutate(auction.end, |ending| {
+				ending.retain(|token| *token != (collection_id, nft_id))
+			});
+			pallet_rmrk_core::Pallet::<T>::unlock_nft(collection_id, nft_id, LockReason::Listed)?;
+
+			Self::deposit_event(Event::AuctionCancelled { collection_id, nft_id });
+
+			Ok(())
+		}
+
+		/// Settle an auction that ended while the market was halted or its collection paused,
+		/// once trading is possible again. Anyone can settle such an auction.
+		///
+		/// Parameters:
+		/// - `origin` - Any account
+		/// - `collection_id` - Collection id of the RMRK NFT
+		/// - `nft_id` - NFT id of the RMRK NFT
+		#[pallet::weight(
+			10_000 + T::DbWeight::get().reads_writes(8, 8) + Pallet::<T>::nft_change_weight()
+		)]
+		#[transactional]
+		pub fn settle_auction(
+			origin: OriginFor<T>,
+			collection_id: CollectionId,
+			nft_id: NftId,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+			Self::ensure_trading(collection_id)?;
+			let auction =
+				Auctions::<T>::get(collection_id, nft_id).ok_or(Error::<T>::UnknownAuction)?;
+			ensure!(
+				auction.end <= <frame_system::Pallet<T>>::block_number(),
+				Error::<T>::AuctionNotEnded
+			);
+
+			Self::finish_auction(collection_id, nft_id);
+
+			Ok(())
+		}
+
+		/// Mint a RMRK NFT from a voucher signed off-chain by the collection issuer, paying its
+		/// price to the issuer. The issuer only pays the mint deposit for NFTs that are sold. A
+		/// voucher can be redeemed once, until it expires.
+		///
+		/// Parameters:
+		/// - `origin` - Account of the buyer, who owns the minted RMRK NFT
+		/// - `voucher` - Mint voucher of the collection issuer
+		/// - `signature` - Signature by the collection issuer of the SCALE encoded tuple of
+		///   `b"rmrk/voucher"`, the genesis hash and the voucher
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(4,5))]
+		#[transactional]
+		pub fn redeem_voucher(
+			origin: OriginFor<T>,
+			voucher: MintVoucherOf<T>,
+			signature: T::OffchainSignature,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let collection_id = voucher.collection_id;
+			Self::ensure_trading(collection_id)?;
+			let issuer = pallet_uniques::Pallet::<T>::class_owner(&collection_id)
+				.ok_or(Error::<T>::CollectionDoesNotExist)?;
+			// The voucher is signed for this pallet on this chain only
+			let payload = (b"rmrk/voucher", Self::genesis_hash(), &voucher).encode();
+			ensure!(signature.verify(&payload[..], &issuer), Error::<T>::InvalidSignature);
+			ensure!(
+				voucher.expires > <frame_system::Pallet<T>>::block_number(),
+				Error::<T>::VoucherExpired
+			);
+			// Prevent replaying the voucher
+			ensure!(
+				!RedeemedVouchers::<T>::contains_key(&issuer, voucher.nonce),
+				Error::<T>::VoucherAlreadyRedeemed
+			);
+			RedeemedVouchers::<T>::insert(&issuer, voucher.nonce, ());
+
+			Self::transfer_funds(voucher.asset_id, &sender, &issuer, voucher.price)?;
+
+			// Mint as `mint_nft` of RMRK Core does, with the issuer receiving the royalty
+			let (collection_id, nft_id) = pallet_rmrk_core::Pallet::<T>::nft_mint(
+				issuer.clone(),
+				sender.clone(),
+				collection_id,
+				Some(issuer.clone()),
+				voucher.royalty,
+				voucher.metadata,
+			)?;
+			pallet_uniques::Pallet::<T>::do_mint(
+				collection_id,
+				nft_id,
+				sender.clone(),
+				|_details| Ok(()),
+			)?;
+			Self::record_sale(collection_id, nft_id, voucher.price, voucher.asset_id);
+
+			Self::deposit_event(Event::VoucherRedeemed {
+				issuer,
+				buyer: sender,
+				collection_id,
+				nft_id,
+				price: voucher.price,
+				nonce: voucher.nonce,
+			});
+
+			Ok(())
+		}
+
+		/// Fill a listing or bid signed off-chain by its maker. A listing is bought by the sender,
+		/// a bid is accepted by the sender as the owner of the NFT. The signature covers the order,
+		/// i.e. its kind, maker, collection, NFT, price, asset, expiry and nonce.
+		///
+		/// Parameters:
+		/// - `origin` - Account of the taker of the order
+		/// - `order` - Order signed by its maker
+		/// - `signature` - Signature by the maker of the SCALE encoded tuple of `b"rmrk/order"`,
+		///   the genesis hash and the order
+		#[pallet::weight(
+			10_000 + T::DbWeight::get().reads_writes(4, 4) + Pallet::<T>::nft_change_weight()
+		)]
+		#[transactional]
+		pub fn fill_order(
+			origin: OriginFor<T>,
+			order: OrderOf<T>,
+			signature: T::OffchainSignature,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			// The order is signed for this pallet on this chain only
+			let payload = (b"rmrk/order", Self::genesis_hash(), &order).encode();
+			ensure!(signature.verify(&payload[..], &order.maker), Error::<T>::InvalidSignature);
+			ensure!(
+				order.expires > <frame_system::Pallet<T>>::block_number(),
+				Error::<T>::OrderExpired
+			);
+			// Prevent filling a cancelled order, or an order twice
+			ensure!(
+				order.nonce >= MinOrderNonce::<T>::get(&order.maker) &&
+					!UsedOrderNonces::<T>::contains_key(&order.maker, order.nonce),
+				Error::<T>::InvalidOrderNonce
+			);
+			UsedOrderNonces::<T>::insert(&order.maker, order.nonce, ());
+
+			let (owner, buyer) = match order.kind {
+				OrderKind::Listing => (order.maker.clone(), sender.clone()),
+				OrderKind::Bid => (sender.clone(), order.maker.clone()),
+			};
+			ensure!(
+				Self::seller_of(order.collection_id, order.nft_id)? == owner,
+				Error::<T>::NoPermission
+			);
+			// The order is settled like an accepted offer of the buyer
+			Self::do_buy(
+				buyer.clone(),
+				order.collection_id,
+				order.nft_id,
+				None,
+				Some(Offer {
+					maker: buyer,
+					amount: order.price,
+					asset_id: order.asset_id,
+					expires: None,
+					recipient: None,
+				}),
+				None,
+			)?;
+
+			Self::deposit_event(Event::OrderFilled {
+				maker: order.maker,
+				taker: sender,
+				collection_id: order.collection_id,
+				nft_id: order.nft_id,
+				price: order.price,
+				nonce: order.nonce,
+			});
+
+			Ok(())
+		}
+
+		/// Cancel an order signed off-chain, so it can't be filled anymore.
+		///
+		/// Parameters:
+		/// - `origin` - Account of the maker of the order
+		/// - `nonce` - Nonce of the order
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2,1))]
+		pub fn cancel_order(origin: OriginFor<T>, nonce: u64) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			ensure!(
+				nonce >= MinOrderNonce::<T>::get(&sender) &&
+					!UsedOrderNonces::<T>::contains_key(&sender, nonce),
+				Error::<T>::InvalidOrderNonce
+			);
+
+			UsedOrderNonces::<T>::insert(&sender, nonce, ());
+
+			Self::deposit_event(Event::OrderCancelled { maker: sender, nonce });
+
+			Ok(())
+		}
+
+		/// Cancel all orders signed off-chain with a nonce below `min_nonce` at once.
+		///
+		/// Parameters:
+		/// - `origin` - Account of the maker of the orders
+		/// - `min_nonce` - Lowest nonce of the orders that can still be filled, above the current
+		///   one
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1))]
+		pub fn bump_order_nonce(origin: OriginFor<T>, min_nonce: u64) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			ensure!(min_nonce > MinOrderNonce::<T>::get(&sender), Error::<T>::InvalidOrderNonce);
+
+			MinOrderNonce::<T>::insert(&sender, min_nonce);
+
+			Self::deposit_event(Event::OrderNonceBumped { maker: sender, min_nonce });
+
+			Ok(())
+		}
+
+		/// Set the marketplace fee charged on every sale.
+		///
+		/// Parameters:
+		/// - `origin` - Must be the `ProtocolOrigin`
+		/// - `fee` - The new marketplace fee
+		#[pallet::weight(10_000 + T::DbWeight::get().writes(1))]
+		pub fn set_fee(origin: OriginFor<T>, fee: Permill) -> DispatchResult {
+			T::ProtocolOrigin::ensure_origin(origin)?;
+
+			MarketplaceFee::<T>::put(fee);
+
+			Self::deposit_event(Event::MarketplaceFeeSet { fee });
+
+			Ok(())
+		}
+
+		/// Halt or resume trading on the whole marketplace, e.g. for incident response. While
+		/// halted, NFTs cannot be listed, bought, offered on or auctioned. Listings and offers
+		/// can still be cancelled.
+		///
+		/// Parameters:
+		/// - `origin` - Must be the `ProtocolOrigin`
+		/// - `halted` - Whether trading is halted
+		#[pallet::weight(10_000 + T::DbWeight::get().writes(1))]
+		pub fn set_market_halted(origin: OriginFor<T>, halted: bool) -> DispatchResult {
+			T::ProtocolOrigin::ensure_origin(origin)?;
+
+			MarketHalted::<T>::put(halted);
+
+			Self::deposit_event(Event::MarketHaltSet { halted });
+
+			Ok(())
+		}
+
+		/// Pause or resume trading of a collection, e.g. when its metadata is compromised. While
+		/// paused, NFTs of the collection cannot be listed, bought, offered on or auctioned.
+		///
+		/// Parameters:
+		/// - `origin` - Must be the `ProtocolOrigin` or the issuer of the collection
+		/// - `collection_id` - Collection id of the RMRK NFTs
+		/// - `paused` - Whether trading of the collection is paused
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1))]
+		pub fn set_collection_paused(
+			origin: OriginFor<T>,
+			collection_id: CollectionId,
+			paused: bool,
+		) -> DispatchResult {
+			let collection = pallet_rmrk_core::Collections::<T>::get(collection_id)
+				.ok_or(Error::<T>::CollectionDoesNotExist)?;
+			if let Err(origin) = T::ProtocolOrigin::try_origin(origin) {
+				let sender = ensure_signed(origin)?;
+				ensure!(sender == collection.issuer, Error::<T>::NoPermission);
+			}
+
+			if paused {
+				PausedCollections::<T>::insert(collection_id, ());
+			} else {
+				PausedCollections::<T>::remove(collection_id);
+			}
+
+			Self::deposit_event(Event::CollectionPauseSet { collection_id, paused });
+
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T>
+where
+	T: pallet_uniques::Config<ClassId = CollectionId, InstanceId = NftId>,
+{
+	/// Buy the NFT helper funciton logic to handle the transactional calls of `buy`,
+	/// `accept_offer` and `accept_collection_offer`
+	///
+	/// Parameters:
+	/// - `buyer`: The account that is buying the RMRK NFT
+	/// - `collection_id`: The collection id of the RMRK NFT
+	/// - `nft_id`: The id of the RMRK NFT
+	/// - `amount`: Optional amount at which the buyer purchased a RMRK NFT
+	/// - `offer`: The accepted offer, already removed from storage, or `None` to buy the listing
+	/// - `recipient`: Optional account or NFT receiving the RMRK NFT instead of the buyer
+	fn do_buy(
+		buyer: T::AccountId,
+		collection_id: CollectionId,
+		nft_id: NftId,
+		amount: Option<BalanceOf<T>>,
+		offer: Option<OfferOf<T>>,
+		recipient: Option<AccountIdOrCollectionNftTuple<T::AccountId>>,
+	) -> DispatchResult {
+		Self::ensure_trading(collection_id)?;
+		// Ensure buyer is not the root owner, or the seller of an escrowed NFT
+		let owner = Self::seller_of(collection_id, nft_id)?;
+		ensure!(buyer != owner, Error::<T>::CannotBuyOwnToken);
+		// Auctioned tokens can only be sold by settling the auction
+		ensure!(!Auctions::<T>::contains_key(collection_id, nft_id), Error::<T>::TokenInAuction);
+
+		let (list_price, asset_id, is_dutch) = if let Some(offer) = offer {
+			(offer.amount, offer.asset_id, false)
+		} else {
+			let list_info =
+				ListedNfts::<T>::take(collection_id, nft_id).ok_or(Error::<T>::TokenNotForSale)?;
+			// Ensure that the current owner is the one that listed the NFT
+			ensure!(list_info.listed_by == owner, Error::<T>::TokenNotForSale);
+			// Ensure a private listing is reserved for the buyer
+			if let Some(buyers) = &list_info.buyers {
+				ensure!(buyers.contains(&buyer), Error::<T>::NotReservedBuyer);
+			}
+			// Ensure the listing has not expired if Some(expires)
+			// if None then there is no expiration
+			if let Some(expires) = list_info.expires {
 				ensure!(
 					expires > <frame_system::Pallet<T>>::block_number(),
 					Error::<T>::ListingHasExpired
 				);
 			}
-			list_info.amount
+			// A Dutch auction is charged the price at the current block
+			match list_info.dutch {
+				Some(dutch) =>
+					(Self::dutch_price(list_info.amount, &dutch), list_info.asset_id, true),
+				None => (list_info.amount, list_info.asset_id, false),
+			}
+		};
+		if let Some(amount) = amount {
+			if is_dutch {
+				// Amount is the maximum price the buyer pays for a Dutch auction
+				ensure!(list_price <= amount, Error::<T>::PriceAboveMaximum);
+			} else {
+				// Check if list_price is equal to amount to prevent front running a buy
+				ensure!(list_price == amount, Error::<T>::PriceDiffersFromExpected);
+			}
+		}
+
+		Self::do_sale(owner, buyer.clone(), collection_id, nft_id, list_price, asset_id)?;
+
+		// The buyer sends the NFT on, so sending to an NFT of another account needs approval
+		if let Some(recipient) = recipient {
+			if recipient != AccountIdOrCollectionNftTuple::AccountId(buyer.clone()) {
+				let buyer_origin = T::Origin::from(RawOrigin::Signed(buyer));
+				pallet_rmrk_core::Pallet::<T>::send(
+					buyer_origin,
+					collection_id,
+					nft_id,
+					recipient,
+				)?;
+			}
+		}
+
+		Ok(())
+	}
+
+	/// Transfer the price from the buyer, paying out the marketplace fee and royalty, and send
+	/// the NFT to the buyer
+	///
+	/// Parameters:
+	/// - `owner`: The account that is selling the RMRK NFT
+	/// - `buyer`: The account that is buying the RMRK NFT
+	/// - `collection_id`: The collection id of the RMRK NFT
+	/// - `nft_id`: The id of the RMRK NFT
+	/// - `list_price`: The price the RMRK NFT is sold at
+	/// - `asset_id`: The asset the price is in, the native currency if `None`
+	fn do_sale(
+		owner: T::AccountId,
+		buyer: T::AccountId,
+		collection_id: CollectionId,
+		nft_id: NftId,
+		list_price: BalanceOf<T>,
+		asset_id: Option<T::AssetId>,
+	) -> DispatchResult {
+		// An escrowed NFT is sent by the escrow account on behalf of the owner
+		let holder = match EscrowedNfts::<T>::take(&owner, (collection_id, nft_id)) {
+			Some(()) => Self::nft_escrow_account(),
+			None => owner.clone(),
+		};
+		let owner_origin = T::Origin::from(RawOrigin::Signed(holder));
+
+		// The sale consumes any listing, release its lock to facilitate the purchase
+		ListedNfts::<T>::remove(collection_id, nft_id);
+		pallet_rmrk_core::Pallet::<T>::unlock_nft(collection_id, nft_id, LockReason::Listed)?;
+
+		// Pay the marketplace fee to the fee account, unless the fee cannot create the account
+		let fee_account = T::MarketplaceFeeAccount::get();
+		let mut fee_amount = Self::marketplace_fee().mul_floor(list_price);
+		if !Self::can_receive(asset_id, &fee_account, fee_amount) {
+			fee_amount = Zero::zero();
+		}
+		if !fee_amount.is_zero() {
+			Self::transfer_funds(asset_id, &buyer, &fee_account, fee_amount)?;
+			Self::deposit_event(Event::MarketplaceFeePaid {
+				collection_id,
+				nft_id,
+				amount: fee_amount,
+			});
+		}
+		let remaining = list_price.saturating_sub(fee_amount);
+
+		// Pay the royalty share to the royalty recipient, the rest goes to the owner. The
+		// royalty is rounded down so any remainder stays with the owner
+		let nft = pallet_rmrk_core::Nfts::<T>::get(collection_id, nft_id)
+			.ok_or(Error::<T>::TokenDoesNotExist)?;
+		let mut royalty_amount = if nft.recipient == owner {
+			Zero::zero()
+		} else {
+			nft.royalty.mul_floor(list_price).min(remaining)
+		};
+		// A royalty that cannot create the account of the royalty recipient also stays with the
+		// owner, rather than failing the sale
+		if !Self::can_receive(asset_id, &nft.recipient, royalty_amount) {
+			royalty_amount = Zero::zero();
+		}
+		if !royalty_amount.is_zero() {
+			Self::transfer_funds(asset_id, &buyer, &nft.recipient, royalty_amount)?;
+			Self::deposit_event(Event::RoyaltyPaid {
+				recipient: nft.recipient,
+				collection_id,
+				nft_id,
+				amount: royalty_amount,
+			});
+		}
+
+		// Transfer currency then transfer the NFT
+		Self::transfer_funds(asset_id, &buyer, &owner, remaining.saturating_sub(royalty_amount))?;
+
+		// A nested NFT leaves its parent, so it cannot stay equipped
+		T::Equip::unequip_nft(collection_id, nft_id)?;
+		let new_owner = AccountIdOrCollectionNftTuple::AccountId(buyer.clone());
+		pallet_rmrk_core::Pallet::<T>::send(owner_origin, collection_id, nft_id, new_owner)?;
+
+		Self::record_sale(collection_id, nft_id, list_price, asset_id);
+
+		Self::deposit_event(Event::TokenSold {
+			owner,
+			buyer,
+			collection_id,
+			nft_id,
+			price: list_price,
+		});
+
+		Ok(())
+	}
+
+	/// Finish an ended auction by selling the NFT to the highest bidder. An auction without
+	/// bids, or on an NFT that was burned or moved in the meantime, is cancelled and the highest
+	/// bidder refunded. If the highest bid cannot be refunded, the auction is kept to be settled
+	/// through `settle_auction` later.
+	///
+	/// Parameters:
+	/// - `collection_id`: The collection id of the RMRK NFT
+	/// - `nft_id`: The id of the RMRK NFT
+	fn finish_auction(collection_id: CollectionId, nft_id: NftId) {
+		let auction = match Auctions::<T>::take(collection_id, nft_id) {
+			Some(auction) => auction,
+			None => return,
+		};
+
+		if let Some((winner, price)) = auction.highest_bid.clone() {
+			let settled = with_transaction(|| {
+				let result =
+					Self::unreserve_funds(auction.asset_id, &winner, price).and_then(|_| {
+						Self::do_settle(
+							&auction.owner,
+							&winner,
+							collection_id,
+							nft_id,
+							price,
+							auction.asset_id,
+						)
+					});
+				if result.is_ok() {
+					TransactionOutcome::Commit(result)
+				} else {
+					TransactionOutcome::Rollback(result)
+				}
+			});
+			if settled.is_ok() {
+				Self::deposit_event(Event::AuctionSettled {
+					owner: auction.owner,
+					winner,
+					collection_id,
+					nft_id,
+					price,
+				});
+				return
+			}
+			if Self::unreserve_funds(auction.asset_id, &winner, price).is_err() {
+				Auctions::<T>::insert(collection_id, nft_id, auction);
+				Self::deposit_event(Event::AuctionSettlementDeferred { collection_id, nft_id });
+				return
+			}
+		}
+
+		// Release the NFT, it may not exist anymore
+		let _ =
+			pallet_rmrk_core::Pallet::<T>::unlock_nft(collection_id, nft_id, LockReason::Listed);
+		Self::deposit_event(Event::AuctionCancelled { collection_id, nft_id });
+	}
+
+	/// Sell an auctioned NFT to the winner if it is still owned by the auction owner
+	///
+	/// Parameters:
+	/// - `owner`: The account that created the auction
+	/// - `winner`: The highest bidder of the auction
+	/// - `collection_id`: The collection id of the RMRK NFT
+	/// - `nft_id`: The id of the RMRK NFT
+	/// - `price`: The highest bid
+	/// - `asset_id`: The asset the bids are in, the native currency if `None`
+	fn do_settle(
+		owner: &T::AccountId,
+		winner: &T::AccountId,
+		collection_id: CollectionId,
+		nft_id: NftId,
+		price: BalanceOf<T>,
+		asset_id: Option<T::AssetId>,
+	) -> DispatchResult {
+		ensure!(
+			pallet_uniques::Pallet::<T>::owner(collection_id, nft_id).as_ref() == Some(owner),
+			Error::<T>::TokenDoesNotExist
+		);
+		Self::do_sale(owner.clone(), winner.clone(), collection_id, nft_id, price, asset_id)
+	}
+
+	/// Price of a Dutch auction listing at the current block
+	///
+	/// Parameters:
+	/// - `start_price`: The price until the start of the Dutch auction
+	/// - `dutch`: The Dutch auction
+	fn dutch_price(
+		start_price: BalanceOf<T>,
+		dutch: &DutchAuction<BalanceOf<T>, T::BlockNumber>,
+	) -> BalanceOf<T> {
+		let now = <frame_system::Pallet<T>>::block_number();
+		if now <= dutch.start {
+			return start_price
+		}
+		if now >= dutch.end {
+			return dutch.floor_price
+		}
+
+		let mut elapsed = now - dutch.start;
+		if !dutch.step.is_zero() {
+			elapsed -= elapsed % dutch.step;
+		}
+		let decline = Perbill::from_rational(elapsed, dutch.end - dutch.start)
+			.mul_floor(start_price.saturating_sub(dutch.floor_price));
+		start_price.saturating_sub(decline)
+	}
+
+	/// Check up to `limit` listings for expiry, continuing after the last listing checked in a
+	/// previous block. Expired listings are removed and their NFTs unlocked.
+	///
+	/// Parameters:
+	/// - `now`: The current block number
+	/// - `limit`: The maximum number of listings to check
+	///
+	/// Output:
+	/// The number of listings checked
+	fn sweep_expired_listings(now: T::BlockNumber, limit: usize) -> usize {
+		if limit == 0 {
+			return 0
+		}
+
+		let listings: Vec<_> = match ListingsSweepCursor::<T>::get() {
+			Some((collection_id, nft_id)) =>
+				ListedNfts::<T>::iter_from(ListedNfts::<T>::hashed_key_for(collection_id, nft_id))
+					.take(limit)
+					.collect(),
+			None => ListedNfts::<T>::iter().take(limit).collect(),
+		};
+
+		// Restart from the first listing once all listings have been checked
+		match listings.last() {
+			Some((collection_id, nft_id, _)) if listings.len() == limit =>
+				ListingsSweepCursor::<T>::put((*collection_id, *nft_id)),
+			_ => ListingsSweepCursor::<T>::kill(),
+		}
+
+		let checked = listings.len();
+		for (collection_id, nft_id, list_info) in listings {
+			if list_info.expires.map_or(false, |expires| expires <= now) {
+				// Return an escrowed NFT to the owner, it cannot be locked or moved meanwhile
+				let _ = Self::release_escrow(&list_info.listed_by, collection_id, nft_id);
+				ListedNfts::<T>::remove(collection_id, nft_id);
+				// The lock has expired as well, only its record is left
+				let _ = pallet_rmrk_core::Pallet::<T>::unlock_nft(
+					collection_id,
+					nft_id,
+					LockReason::Listed,
+				);
+				Self::deposit_event(Event::ListingExpired {
+					owner: list_info.listed_by,
+					collection_id,
+					nft_id,
+				});
+			}
+		}
+		checked
+	}
+
+	/// Check up to `limit` offers for expiry, continuing after the last offer checked in a
+	/// previous block. Expired offers are removed and their amounts unreserved.
+	///
+	/// Parameters:
+	/// - `now`: The current block number
+	/// - `limit`: The maximum number of offers to check
+	///
+	/// Output:
+	/// The number of offers checked
+	fn sweep_expired_offers(now: T::BlockNumber, limit: usize) -> usize {
+		if limit == 0 {
+			return 0
+		}
+
+		let offers: Vec<_> = match OffersSweepCursor::<T>::get() {
+			Some((token_id, offerer)) =>
+				Offers::<T>::iter_from(Offers::<T>::hashed_key_for(token_id, offerer))
+					.take(limit)
+					.collect(),
+			None => Offers::<T>::iter().take(limit).collect(),
 		};
-		// Check if list_price is equal to amount to prevent front running a buy
-		if let Some(amount) = amount {
-			ensure!(list_price == amount, Error::<T>::PriceDiffersFromExpected);
+
+		// Restart from the first offer once all offers have been checked
+		match offers.last() {
+			Some((token_id, offerer, _)) if offers.len() == limit =>
+				OffersSweepCursor::<T>::put((*token_id, offerer.clone())),
+			_ => OffersSweepCursor::<T>::kill(),
 		}
-		// TODO: Set NFT Lock status to false to facilitate the purchase
 
-		// Transfer currency then transfer the NFT
-		<T as pallet::Config>::Currency::transfer(
-			&buyer,
-			&owner,
-			list_price,
-			ExistenceRequirement::KeepAlive,
-		)?;
+		let checked = offers.len();
+		for ((collection_id, nft_id), offerer, offer) in offers {
+			// An offer that cannot be refunded is kept, so its amount is not lost
+			if offer.expires.map_or(false, |expires| expires <= now) &&
+				Self::unreserve_funds(offer.asset_id, &offer.maker, offer.amount).is_ok()
+			{
+				Offers::<T>::remove((collection_id, nft_id), &offerer);
+				Self::decrement_offers_count((collection_id, nft_id));
+				Self::deposit_event(Event::OfferExpired { offerer, collection_id, nft_id });
+			}
+		}
+		checked
+	}
 
-		let new_owner = AccountIdOrCollectionNftTuple::AccountId(buyer.clone());
-		pallet_rmrk_core::Pallet::<T>::send(owner_origin, collection_id, nft_id, new_owner)?;
+	/// Check up to `limit` collection offers for expiry, continuing after the last collection
+	/// offer checked in a previous block. Expired collection offers are removed and their amounts
+	/// unreserved.
+	///
+	/// Parameters:
+	/// - `now`: The current block number
+	/// - `limit`: The maximum number of collection offers to check
+	///
+	/// Output:
+	/// The number of collection offers checked
+	fn sweep_expired_collection_offers(now: T::BlockNumber, limit: usize) -> usize {
+		if limit == 0 {
+			return 0
+		}
 
-		Self::deposit_event(Event::TokenSold {
-			owner,
-			buyer,
-			collection_id,
-			nft_id,
-			price: list_price,
-		});
+		let offers: Vec<_> = match CollectionOffersSweepCursor::<T>::get() {
+			Some((collection_id, offerer)) => CollectionOffers::<T>::iter_from(
+				CollectionOffers::<T>::hashed_key_for(collection_id, offerer),
+			)
+			.take(limit)
+			.collect(),
+			None => CollectionOffers::<T>::iter().take(limit).collect(),
+		};
 
-		Ok(())
+		// Restart from the first collection offer once all have been checked
+		match offers.last() {
+			Some((collection_id, offerer, _)) if offers.len() == limit =>
+				CollectionOffersSweepCursor::<T>::put((*collection_id, offerer.clone())),
+			_ => CollectionOffersSweepCursor::<T>::kill(),
+		}
+
+		let checked = offers.len();
+		for (collection_id, offerer, offer) in offers {
+			// A collection offer that cannot be refunded is kept, so its amount is not lost
+			if offer.expires.map_or(false, |expires| expires <= now) &&
+				Self::unreserve_funds(offer.asset_id, &offer.maker, offer.amount).is_ok()
+			{
+				CollectionOffers::<T>::remove(collection_id, &offerer);
+				Self::deposit_event(Event::CollectionOfferExpired { offerer, collection_id });
+			}
+		}
+		checked
+	}
+
+	/// Check up to `limit` bundles for expiry, continuing after the last bundle checked in a
+	/// previous block. Expired bundles are removed and their NFTs unlocked.
+	///
+	/// Parameters:
+	/// - `now`: The current block number
+	/// - `limit`: The maximum number of bundles to check
+	///
+	/// Output:
+	/// The number of bundles checked
+	fn sweep_expired_bundles(now: T::BlockNumber, limit: usize) -> usize {
+		if limit == 0 {
+			return 0
+		}
+
+		let bundles: Vec<_> = match BundlesSweepCursor::<T>::get() {
+			Some(bundle_id) => Bundles::<T>::iter_from(Bundles::<T>::hashed_key_for(bundle_id))
+				.take(limit)
+				.collect(),
+			None => Bundles::<T>::iter().take(limit).collect(),
+		};
+
+		// Restart from the first bundle once all bundles have been checked
+		match bundles.last() {
+			Some((bundle_id, _)) if bundles.len() == limit =>
+				BundlesSweepCursor::<T>::put(bundle_id),
+			_ => BundlesSweepCursor::<T>::kill(),
+		}
+
+		let checked = bundles.len();
+		for (bundle_id, bundle) in bundles {
+			if bundle.expires.map_or(false, |expires| expires <= now) {
+				Self::release_bundle(bundle_id);
+				Self::deposit_event(Event::BundleExpired { owner: bundle.listed_by, bundle_id });
+			}
+		}
+		checked
 	}
 
 	/// Helper function to check if a RMRK NFT is listed
@@ -518,6 +2752,81 @@ where
 		ListedNfts::<T>::contains_key(collection_id, nft_id)
 	}
 
+	/// Remove a bundle and release its NFTs
+	///
+	/// Parameters:
+	/// - bundle_id: The id of the bundle
+	fn remove_bundle(bundle_id: BundleId) {
+		if let Some(bundle) = Self::release_bundle(bundle_id) {
+			Self::deposit_event(Event::BundleUnlisted { owner: bundle.listed_by, bundle_id });
+		}
+	}
+
+	/// Remove a bundle and unlock its NFTs without emitting an event
+	///
+	/// Parameters:
+	/// - bundle_id: The id of the bundle
+	///
+	/// Output:
+	/// The removed bundle, if it existed
+	fn release_bundle(bundle_id: BundleId) -> Option<BundleInfoOf<T>> {
+		let bundle = Bundles::<T>::take(bundle_id)?;
+		for (collection_id, nft_id) in bundle.items.iter() {
+			BundledNfts::<T>::remove(collection_id, nft_id);
+			// Release the NFT, it may not exist anymore
+			let _ = pallet_rmrk_core::Pallet::<T>::unlock_nft(
+				*collection_id,
+				*nft_id,
+				LockReason::Listed,
+			);
+		}
+		Some(bundle)
+	}
+
+	/// Remove the listing of a RMRK NFT and the bundle it is part of, if any, releasing its lock
+	///
+	/// Parameters:
+	/// - collection_id: The collection id of the RMRK NFT
+	/// - nft_id: The nft id of the RMRK NFT
+	fn clear_listing(collection_id: CollectionId, nft_id: NftId) -> DispatchResult {
+		if Self::is_nft_listed(collection_id, nft_id) {
+			ListedNfts::<T>::remove(collection_id, nft_id);
+			pallet_rmrk_core::Pallet::<T>::unlock_nft(collection_id, nft_id, LockReason::Listed)?;
+		}
+		Self::unlist_bundle_of(collection_id, nft_id);
+		Ok(())
+	}
+
+	/// Remove the bundle a RMRK NFT is part of, if any
+	///
+	/// Parameters:
+	/// - collection_id: The collection id of the RMRK NFT
+	/// - nft_id: The nft id of the RMRK NFT
+	fn unlist_bundle_of(collection_id: CollectionId, nft_id: NftId) {
+		if let Some(bundle_id) = BundledNfts::<T>::get(collection_id, nft_id) {
+			Self::remove_bundle(bundle_id);
+		}
+	}
+
+	/// Ensure the recipient of an offer is the account of its maker or an NFT the maker owns
+	///
+	/// Parameters:
+	/// - maker: The account making the offer
+	/// - recipient: The account or NFT receiving the RMRK NFT, the maker's account if `None`
+	fn ensure_offer_recipient(
+		maker: &T::AccountId,
+		recipient: &Option<AccountIdOrCollectionNftTuple<T::AccountId>>,
+	) -> DispatchResult {
+		match recipient {
+			Some(AccountIdOrCollectionNftTuple::AccountId(account)) =>
+				ensure!(account == maker, Error::<T>::NoPermission),
+			Some(AccountIdOrCollectionNftTuple::CollectionAndNftTuple(cid, nid)) =>
+				ensure!(&Self::root_owner(*cid, *nid)? == maker, Error::<T>::NoPermission),
+			None => (),
+		}
+		Ok(())
+	}
+
 	/// Helper function to check if an account has already submitted an offer on a RMRK NFT
 	///
 	/// Parameters:
@@ -528,6 +2837,141 @@ where
 		Offers::<T>::contains_key((collection_id, nft_id), sender)
 	}
 
+	/// Helper function to get the account at the root of the NFT's ownership tree
+	///
+	/// Parameters:
+	/// - collection_id: The collection id of the RMRK NFT
+	/// - nft_id: The nft id of the RMRK NFT
+	fn root_owner(collection_id: CollectionId, nft_id: NftId) -> Result<T::AccountId, Error<T>> {
+		pallet_rmrk_core::Pallet::<T>::lookup_root_owner(collection_id, nft_id)
+			.map(|(root_owner, _)| root_owner)
+			.map_err(|_| Error::<T>::TokenDoesNotExist)
+	}
+
+	/// Lowest price an NFT of a collection can currently be bought at, ignoring private, expired
+	/// and stale listings. Dutch auctions count at their price at the current block.
+	///
+	/// Parameters:
+	/// - collection_id: The collection id of the RMRK NFTs
+	/// - asset_id: The asset of the listings, the native currency if `None`
+	pub fn collection_floor(
+		collection_id: CollectionId,
+		asset_id: Option<T::AssetId>,
+	) -> Option<BalanceOf<T>> {
+		let now = <frame_system::Pallet<T>>::block_number();
+		ListedNfts::<T>::iter_prefix(collection_id)
+			.filter(|(nft_id, list_info)| {
+				list_info.asset_id == asset_id &&
+					list_info.buyers.is_none() &&
+					list_info.expires.map_or(true, |expires| expires > now) &&
+					Self::root_owner(collection_id, *nft_id).ok().as_ref() ==
+						Some(&list_info.listed_by)
+			})
+			.map(|(_, list_info)| match list_info.dutch {
+				Some(dutch) => Self::dutch_price(list_info.amount, &dutch),
+				None => list_info.amount,
+			})
+			.min()
+	}
+
+	/// Whether a RMRK NFT is nested in one of the given RMRK NFTs, or one of them in it
+	///
+	/// Parameters:
+	/// - collection_id: The collection id of the RMRK NFT
+	/// - nft_id: The nft id of the RMRK NFT
+	/// - others: The collection and nft ids of the other RMRK NFTs
+	fn is_nested_with(
+		collection_id: CollectionId,
+		nft_id: NftId,
+		others: &[(CollectionId, NftId)],
+	) -> bool {
+		others.iter().any(|&(other_collection_id, other_nft_id)| {
+			pallet_rmrk_core::Pallet::<T>::is_x_descendent_of_y(
+				collection_id,
+				nft_id,
+				other_collection_id,
+				other_nft_id,
+			) || pallet_rmrk_core::Pallet::<T>::is_x_descendent_of_y(
+				other_collection_id,
+				other_nft_id,
+				collection_id,
+				nft_id,
+			)
+		})
+	}
+
+	///
+	/// Parameters:
+	/// - collection_id: The collection id of the RMRK NFT
+	/// - nft_id: The nft id of the RMRK NFT
+	fn seller_of(collection_id: CollectionId, nft_id: NftId) -> Result<T::AccountId, Error<T>> {
+		let owner = Self::root_owner(collection_id, nft_id)?;
+		if owner == Self::nft_escrow_account() {
+			if let Some(list_info) = ListedNfts::<T>::get(collection_id, nft_id) {
+				if EscrowedNfts::<T>::contains_key(&list_info.listed_by, (collection_id, nft_id)) {
+					return Ok(list_info.listed_by)
+				}
+			}
+		}
+		Ok(owner)
+	}
+
+	/// Send a RMRK NFT held in escrow back to its seller, if it is held in escrow
+	///
+	/// Parameters:
+	/// - seller: The account that listed the RMRK NFT
+	/// - collection_id: The collection id of the RMRK NFT
+	/// - nft_id: The nft id of the RMRK NFT
+	fn release_escrow(
+		seller: &T::AccountId,
+		collection_id: CollectionId,
+		nft_id: NftId,
+	) -> DispatchResult {
+		if EscrowedNfts::<T>::contains_key(seller, (collection_id, nft_id)) {
+			let escrow_origin = T::Origin::from(RawOrigin::Signed(Self::nft_escrow_account()));
+			let new_owner = AccountIdOrCollectionNftTuple::AccountId(seller.clone());
+			Self::escrow_send(escrow_origin, collection_id, nft_id, new_owner)?;
+			EscrowedNfts::<T>::remove(seller, (collection_id, nft_id));
+		}
+		Ok(())
+	}
+
+	/// Send a RMRK NFT into escrow or back from escrow to its seller, keeping the offers on it
+	///
+	/// Parameters:
+	/// - origin: The account sending the RMRK NFT
+	/// - collection_id: The collection id of the RMRK NFT
+	/// - nft_id: The nft id of the RMRK NFT
+	/// - new_owner: The escrow account or the seller
+	fn escrow_send(
+		origin: T::Origin,
+		collection_id: CollectionId,
+		nft_id: NftId,
+		new_owner: AccountIdOrCollectionNftTuple<T::AccountId>,
+	) -> DispatchResult {
+		EscrowTransfer::<T>::put((collection_id, nft_id));
+		let result = pallet_rmrk_core::Pallet::<T>::send(origin, collection_id, nft_id, new_owner);
+		EscrowTransfer::<T>::kill();
+		result
+	}
+
+	/// Whether a RMRK NFT being sent moves into escrow or back from escrow to its seller
+	///
+	/// Parameters:
+	/// - collection_id: The collection id of the RMRK NFT
+	/// - nft_id: The nft id of the RMRK NFT
+	fn is_escrow_transfer(collection_id: CollectionId, nft_id: NftId) -> bool {
+		EscrowTransfer::<T>::get() == Some((collection_id, nft_id))
+	}
+
+	/// RMRK NFTs of an account held in escrow while listed
+	///
+	/// Parameters:
+	/// - owner: The account that listed the RMRK NFTs
+	pub fn escrowed_nfts_of(owner: &T::AccountId) -> Vec<(CollectionId, NftId)> {
+		EscrowedNfts::<T>::iter_prefix(owner).map(|(token_id, ())| token_id).collect()
+	}
+
 	/// Helper function to check if the NFT's parent is a User Account
 	///
 	/// Parameters:
@@ -545,3 +2989,242 @@ where
 		false
 	}
 }
+
+impl<T: Config> Pallet<T> {
+	/// Remove all offers on a RMRK NFT and unreserve their amounts. An offer that cannot be
+	/// refunded is kept, so its amount is not lost.
+	///
+	/// Parameters:
+	/// - collection_id: The collection id of the RMRK NFT
+	/// - nft_id: The nft id of the RMRK NFT
+	fn refund_offers(collection_id: CollectionId, nft_id: NftId) {
+		let offers: Vec<_> = Offers::<T>::iter_prefix((collection_id, nft_id)).collect();
+		for (offerer, offer) in offers {
+			if Self::unreserve_funds(offer.asset_id, &offer.maker, offer.amount).is_ok() {
+				Offers::<T>::remove((collection_id, nft_id), &offerer);
+				Self::decrement_offers_count((collection_id, nft_id));
+				Self::deposit_event(Event::OfferRefunded { offerer, collection_id, nft_id });
+			}
+		}
+	}
+
+	/// Decrement the number of offers on a RMRK NFT after one of them was removed
+	///
+	/// Parameters:
+	/// - token_id: The collection id and nft id of the RMRK NFT
+	fn decrement_offers_count(token_id: (CollectionId, NftId)) {
+		OffersCount::<T>::mutate_exists(token_id, |count| {
+			*count = count.and_then(|count| count.checked_sub(1)).filter(|count| *count > 0);
+		});
+	}
+
+	/// Worst-case weight of removing the offers on a RMRK NFT and its bundle, as done when the
+	/// NFT is sent or burned
+	pub fn nft_change_weight() -> Weight {
+		let db_weight = T::DbWeight::get();
+		db_weight
+			.reads_writes(2, 2)
+			.saturating_add(
+				db_weight.reads_writes(1, 2).saturating_mul(T::MaxOffersPerNft::get() as Weight),
+			)
+			.saturating_add(
+				db_weight.reads_writes(1, 2).saturating_mul(T::MaxBundleSize::get() as Weight),
+			)
+	}
+
+	/// Get the id of the next bundle
+	fn get_next_bundle_id() -> Result<BundleId, Error<T>> {
+		NextBundleId::<T>::try_mutate(|id| {
+			let current_id = *id;
+			*id = id.checked_add(1).ok_or(Error::<T>::NoAvailableBundleId)?;
+			Ok(current_id)
+		})
+	}
+
+	/// Get the id of the next swap
+	fn get_next_swap_id() -> Result<SwapId, Error<T>> {
+		NextSwapId::<T>::try_mutate(|id| {
+			let current_id = *id;
+			*id = id.checked_add(1).ok_or(Error::<T>::NoAvailableSwapId)?;
+			Ok(current_id)
+		})
+	}
+
+	/// Ensure the marketplace is not halted and trading of a collection is not paused
+	///
+	/// Parameters:
+	/// - collection_id: The collection id of the RMRK NFT
+	fn ensure_trading(collection_id: CollectionId) -> DispatchResult {
+		ensure!(!MarketHalted::<T>::get(), Error::<T>::MarketHalted);
+		ensure!(!PausedCollections::<T>::contains_key(collection_id), Error::<T>::CollectionPaused);
+		Ok(())
+	}
+
+	/// Add a sale to the statistics of its collection, dropping the oldest recent sale once
+	/// `MaxRecentSales` are kept
+	///
+	/// Parameters:
+	/// - collection_id: The collection id of the RMRK NFT
+	/// - nft_id: The nft id of the RMRK NFT
+	/// - price: The price the RMRK NFT was sold at
+	/// - asset_id: The asset the price is in, the native currency if `None`
+	fn record_sale(
+		collection_id: CollectionId,
+		nft_id: NftId,
+		price: BalanceOf<T>,
+		asset_id: Option<T::AssetId>,
+	) {
+		let sale = SaleRecord { nft_id, price, block: <frame_system::Pallet<T>>::block_number() };
+		Stats::<T>::mutate(collection_id, asset_id, |stats| {
+			let stats = stats.get_or_insert_with(|| CollectionStats {
+				last_price: Zero::zero(),
+				volume: Zero::zero(),
+				sale_count: 0,
+				recent_sales: Default::default(),
+			});
+			stats.last_price = price;
+			stats.volume = stats.volume.saturating_add(price);
+			stats.sale_count = stats.sale_count.saturating_add(1);
+			if !stats.recent_sales.is_empty() &&
+				stats.recent_sales.len() >= T::MaxRecentSales::get() as usize
+			{
+				stats.recent_sales.remove(0);
+			}
+			// Only fails if no recent sales are kept at all
+			let _ = stats.recent_sales.try_push(sale);
+		});
+	}
+
+	/// Hash of the genesis block, signed along with vouchers and orders so that a signature is
+	/// only valid on this chain
+	fn genesis_hash() -> T::Hash {
+		<frame_system::Pallet<T>>::block_hash(T::BlockNumber::zero())
+	}
+
+	/// Account holding the RMRK NFTs listed with `list_escrowed`
+	pub fn nft_escrow_account() -> T::AccountId {
+		T::PalletId::get().into_sub_account(b"nft")
+	}
+
+	/// Account holding the assets of offers and bids made in non-native assets
+	pub fn asset_escrow_account() -> T::AccountId {
+		T::PalletId::get().into_sub_account(b"asset")
+	}
+
+	/// Reserve funds of an account, non-native assets are moved to the asset escrow account
+	///
+	/// Parameters:
+	/// - asset_id: The asset to reserve, the native currency if `None`
+	/// - who: The account to reserve the funds of
+	/// - amount: The amount to reserve
+	fn reserve_funds(
+		asset_id: Option<T::AssetId>,
+		who: &T::AccountId,
+		amount: BalanceOf<T>,
+	) -> DispatchResult {
+		match asset_id {
+			None => <T as pallet::Config>::Currency::reserve(who, amount),
+			Some(id) => T::Assets::transfer(id, who, &Self::asset_escrow_account(), amount, false)
+				.map(|_| ()),
+		}
+	}
+
+	/// Release funds reserved with `reserve_funds`
+	///
+	/// Parameters:
+	/// - asset_id: The asset to unreserve, the native currency if `None`
+	/// - who: The account the funds were reserved from
+	/// - amount: The amount to unreserve
+	fn unreserve_funds(
+		asset_id: Option<T::AssetId>,
+		who: &T::AccountId,
+		amount: BalanceOf<T>,
+	) -> DispatchResult {
+		match asset_id {
+			None => {
+				<T as pallet::Config>::Currency::unreserve(who, amount);
+				Ok(())
+			},
+			Some(id) => T::Assets::transfer(id, &Self::asset_escrow_account(), who, amount, false)
+				.map(|_| ()),
+		}
+	}
+
+	/// Whether an offer amount is above the minimum threshold. Offers in the native currency
+	/// must be at least `MinimumOfferAmount`, offers in an asset must be non-zero and at least
+	/// the minimum balance of the asset.
+	///
+	/// Parameters:
+	/// - asset_id: The asset of the offer, the native currency if `None`
+	/// - amount: The amount offered
+	fn is_offer_amount_valid(asset_id: Option<T::AssetId>, amount: BalanceOf<T>) -> bool {
+		match asset_id {
+			None => amount >= T::MinimumOfferAmount::get(),
+			Some(id) => !amount.is_zero() && amount >= T::Assets::minimum_balance(id),
+		}
+	}
+
+	/// Whether an account can be paid an amount, i.e. the account exists or the amount reaches
+	/// the existential deposit
+	///
+	/// Parameters:
+	/// - asset_id: The asset to pay, the native currency if `None`
+	/// - who: The account to pay
+	/// - amount: The amount to pay
+	fn can_receive(asset_id: Option<T::AssetId>, who: &T::AccountId, amount: BalanceOf<T>) -> bool {
+		match asset_id {
+			None =>
+				amount >= <T as pallet::Config>::Currency::minimum_balance() ||
+					!<T as pallet::Config>::Currency::total_balance(who).is_zero(),
+			Some(id) =>
+				amount >= T::Assets::minimum_balance(id) || !T::Assets::balance(id, who).is_zero(),
+		}
+	}
+
+	/// Transfer funds between accounts, keeping the source alive for the native currency
+	///
+	/// Parameters:
+	/// - asset_id: The asset to transfer, the native currency if `None`
+	/// - source: The account paying
+	/// - dest: The account paid
+	/// - amount: The amount to transfer
+	fn transfer_funds(
+		asset_id: Option<T::AssetId>,
+		source: &T::AccountId,
+		dest: &T::AccountId,
+		amount: BalanceOf<T>,
+	) -> DispatchResult {
+		match asset_id {
+			None => <T as pallet::Config>::Currency::transfer(
+				source,
+				dest,
+				amount,
+				ExistenceRequirement::KeepAlive,
+			),
+			Some(id) => T::Assets::transfer(id, source, dest, amount, false).map(|_| ()),
+		}
+	}
+}
+
+impl<T: Config> OnNftChange for Pallet<T>
+where
+	T: pallet_uniques::Config<ClassId = CollectionId, InstanceId = NftId>,
+{
+	fn on_nft_sent(collection_id: CollectionId, nft_id: NftId) {
+		// Offers were made to the previous owner, who is still the seller of an NFT moved into or
+		// out of escrow
+		if !Self::is_escrow_transfer(collection_id, nft_id) {
+			Self::refund_offers(collection_id, nft_id);
+		}
+		Self::unlist_bundle_of(collection_id, nft_id);
+	}
+
+	fn on_nft_burned(collection_id: CollectionId, nft_id: NftId) {
+		Self::refund_offers(collection_id, nft_id);
+		Self::unlist_bundle_of(collection_id, nft_id);
+	}
+
+	fn weight() -> Weight {
+		Self::nft_change_weight()
+	}
+}
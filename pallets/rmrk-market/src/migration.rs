@@ -0,0 +1,63 @@
+use super::*;
+use crate::types::ListInfo;
+use frame_support::{pallet_prelude::*, traits::GetStorageVersion};
+
+/// Listing as stored before storage version 1
+#[derive(Decode)]
+struct OldListInfo<AccountId, Balance, BlockNumber> {
+	listed_by: AccountId,
+	amount: Balance,
+	expires: Option<BlockNumber>,
+}
+
+/// Offer as stored before storage version 1
+#[derive(Decode)]
+struct OldOffer<AccountId, Balance, BlockNumber> {
+	maker: AccountId,
+	amount: Balance,
+	expires: Option<BlockNumber>,
+}
+
+/// Adds the asset, Dutch auction and reserved buyers of listings and the asset and recipient of
+/// offers, all set to `None`, and counts the offers on each NFT in `OffersCount`
+pub fn migrate_to_v1<T: Config>() -> Weight {
+	if Pallet::<T>::on_chain_storage_version() >= 1 {
+		return T::DbWeight::get().reads(1)
+	}
+
+	let mut listings = 0u64;
+	ListedNfts::<T>::translate::<OldListInfo<T::AccountId, BalanceOf<T>, T::BlockNumber>, _>(
+		|_, _, old| {
+			listings = listings.saturating_add(1);
+			Some(ListInfo {
+				listed_by: old.listed_by,
+				amount: old.amount,
+				asset_id: None,
+				expires: old.expires,
+				dutch: None,
+				buyers: None,
+			})
+		},
+	);
+
+	let mut offers = 0u64;
+	Offers::<T>::translate::<OldOffer<T::AccountId, BalanceOf<T>, T::BlockNumber>, _>(
+		|token, _, old| {
+			offers = offers.saturating_add(1);
+			OffersCount::<T>::mutate(token, |count| *count = count.saturating_add(1));
+			Some(Offer {
+				maker: old.maker,
+				amount: old.amount,
+				asset_id: None,
+				expires: old.expires,
+				recipient: None,
+			})
+		},
+	);
+
+	StorageVersion::new(1).put::<Pallet<T>>();
+	T::DbWeight::get().reads_writes(
+		listings.saturating_add(offers.saturating_mul(2)).saturating_add(1),
+		listings.saturating_add(offers.saturating_mul(2)).saturating_add(1),
+	)
+}
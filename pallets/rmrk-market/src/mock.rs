@@ -5,6 +5,7 @@ use frame_support::{
 	construct_runtime, parameter_types,
 	traits::{ConstU32, Everything},
 	weights::Weight,
+	PalletId,
 };
 use frame_system as system;
 use frame_system::EnsureRoot;
@@ -12,7 +13,8 @@ use sp_core::{crypto::AccountId32, H256};
 
 use sp_runtime::{
 	testing::Header,
-	traits::{BlakeTwo256, IdentityLookup},
+	traits::{BlakeTwo256, IdentityLookup, Verify},
+	MultiSignature,
 };
 
 mod rmrk_market {
@@ -34,8 +36,10 @@ construct_runtime!(
 	{
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
 		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Assets: pallet_assets::{Pallet, Call, Storage, Event<T>},
 		Uniques: pallet_uniques::{Pallet, Call, Storage, Event<T>},
 		RmrkCore: pallet_rmrk_core::{Pallet, Call, Event<T>, Storage},
+		RmrkEquip: pallet_rmrk_equip::{Pallet, Call, Storage, Event<T>},
 		RmrkMarket: pallet_rmrk_market::{Pallet, Call, Storage, Event<T>},
 	}
 );
@@ -74,7 +78,7 @@ impl frame_system::Config for Test {
 }
 
 parameter_types! {
-	pub const ExistentialDeposit: u64 = 1;
+	pub static ExistentialDeposit: Balance = 1;
 	pub const MaxReserves: u32 = 50;
 }
 
@@ -90,11 +94,38 @@ impl pallet_balances::Config for Test {
 	type ReserveIdentifier = [u8; 8];
 }
 
+parameter_types! {
+	pub const AssetDeposit: Balance = 100 * RMRK;
+	pub const ApprovalDeposit: Balance = RMRK;
+	pub const AssetsStringLimit: u32 = 50;
+	pub const MetadataDepositBase: Balance = 10 * RMRK;
+	pub const MetadataDepositPerByte: Balance = RMRK;
+}
+
+impl pallet_assets::Config for Test {
+	type Event = Event;
+	type Balance = Balance;
+	type AssetId = u32;
+	type Currency = Balances;
+	type ForceOrigin = EnsureRoot<AccountId>;
+	type AssetDeposit = AssetDeposit;
+	type MetadataDepositBase = MetadataDepositBase;
+	type MetadataDepositPerByte = MetadataDepositPerByte;
+	type ApprovalDeposit = ApprovalDeposit;
+	type StringLimit = AssetsStringLimit;
+	type Freezer = ();
+	type Extra = ();
+	type WeightInfo = ();
+}
+
 parameter_types! {
 	pub MaxMetadataLength: u32 = 256;
 	pub const MaxRecursions: u32 = 10;
 	pub const ResourceSymbolLimit: u32 = 10;
 	pub const CollectionSymbolLimit: u32 = 100;
+	pub const ReturnRejectedNfts: bool = false;
+	pub const MaxChildrenPerNft: u32 = 20;
+	pub const MaxSubtreeSize: u32 = 50;
 }
 
 impl pallet_rmrk_core::Config for Test {
@@ -104,6 +135,21 @@ impl pallet_rmrk_core::Config for Test {
 	type MaxRecursions = MaxRecursions;
 	type ResourceSymbolLimit = ResourceSymbolLimit;
 	type CollectionSymbolLimit = CollectionSymbolLimit;
+	type OnNftChange = RmrkMarket;
+	type ReturnRejectedNfts = ReturnRejectedNfts;
+	type MaxChildrenPerNft = MaxChildrenPerNft;
+	type MaxSubtreeSize = MaxSubtreeSize;
+}
+
+parameter_types! {
+	pub const MaxPartsPerBase: u32 = 5;
+	pub const MaxPropertiesPerTheme: u32 = 5;
+}
+
+impl pallet_rmrk_equip::Config for Test {
+	type Event = Event;
+	type MaxPartsPerBase = MaxPartsPerBase;
+	type MaxPropertiesPerTheme = MaxPropertiesPerTheme;
 }
 
 parameter_types! {
@@ -137,6 +183,16 @@ impl pallet_uniques::Config for Test {
 
 parameter_types! {
 	pub const MinimumOfferAmount: Balance = 50 * UNITS;
+	pub const MaxOffersPerNft: u32 = 2;
+	pub static DefaultMarketplaceFee: Permill = Permill::zero();
+	pub const MarketplaceFeeAccount: AccountId = FEE_ACCOUNT;
+	pub const AuctionExtensionPeriod: u64 = 5;
+	pub const MaxAuctionsPerBlock: u32 = 2;
+	pub const MaxSweepsPerBlock: u32 = 4;
+	pub const MaxBundleSize: u32 = 3;
+	pub const MaxReservedBuyers: u32 = 2;
+	pub const MaxRecentSales: u32 = 2;
+	pub const MarketPalletId: PalletId = PalletId(*b"rmrk/mkt");
 }
 
 impl Config for Test {
@@ -144,11 +200,28 @@ impl Config for Test {
 	type ProtocolOrigin = EnsureRoot<AccountId>;
 	type Currency = Balances;
 	type MinimumOfferAmount = MinimumOfferAmount;
+	type MaxOffersPerNft = MaxOffersPerNft;
+	type MarketplaceFee = DefaultMarketplaceFee;
+	type MarketplaceFeeAccount = MarketplaceFeeAccount;
+	type AuctionExtensionPeriod = AuctionExtensionPeriod;
+	type MaxAuctionsPerBlock = MaxAuctionsPerBlock;
+	type MaxSweepsPerBlock = MaxSweepsPerBlock;
+	type MaxBundleSize = MaxBundleSize;
+	type MaxReservedBuyers = MaxReservedBuyers;
+	type MaxRecentSales = MaxRecentSales;
+	type Equip = RmrkEquip;
+	type AssetId = u32;
+	type Assets = Assets;
+	type PalletId = MarketPalletId;
+	type OffchainSignature = MultiSignature;
+	type OffchainPublic = <MultiSignature as Verify>::Signer;
 }
 
 pub const ALICE: AccountId = AccountId::new([1u8; 32]);
 pub const BOB: AccountId = AccountId::new([2u8; 32]);
 pub const CHARLIE: AccountId = AccountId::new([3u8; 32]);
+pub const DAVE: AccountId = AccountId::new([4u8; 32]);
+pub const FEE_ACCOUNT: AccountId = AccountId::new([9u8; 32]);
 pub const UNITS: Balance = 100_000_000_000;
 pub const RMRK: Balance = 1;
 pub const COLLECTION_ID_0: <Test as pallet_uniques::Config>::ClassId = 0;
@@ -157,6 +230,7 @@ pub const NFT_ID_0: <Test as pallet_uniques::Config>::InstanceId = 0;
 pub const NFT_ID_1: <Test as pallet_uniques::Config>::InstanceId = 1;
 pub const NOT_EXISTING_NFT_ID: <Test as pallet_uniques::Config>::ClassId = 999;
 pub const MIN_OFFER_ON_NFT: Balance = 50 * UNITS;
+pub const ASSET_ID_0: u32 = 0;
 
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
@@ -1,9 +1,14 @@
 use super::*;
 use crate::mock::*;
-use frame_support::{assert_noop, assert_ok};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{OnIdle, OnInitialize},
+};
 use mock::{Event as MockEvent, *};
+use rmrk_traits::{EquippableList, PartType, SlotPart};
 
-use sp_runtime::Permill;
+use sp_core::{crypto::AccountId32, sr25519, Pair};
+use sp_runtime::{traits::BadOrigin, MultiSignature, Permill};
 use sp_std::{convert::TryInto, vec::Vec};
 
 /// Turns a string into a BoundedVec
@@ -44,6 +49,82 @@ fn basic_mint() -> DispatchResult {
 	)
 }
 
+/// Equips NFT [0,1] of ALICE in slot 201 of base 0 of NFT [0,0], nesting it there
+fn equip_nft_1_in_nft_0() {
+	// ALICE creates base 0 with slot 201, which NFTs of collection 0 can be equipped into
+	assert_ok!(RmrkEquip::create_base(
+		Origin::signed(ALICE),
+		bvec![0u8; 3],
+		bvec![0u8; 3],
+		vec![PartType::SlotPart(SlotPart {
+			id: 201,
+			z: 0,
+			src: bvec![0u8; 3],
+			equippable: EquippableList::Custom(vec![COLLECTION_ID_0]),
+		})],
+	));
+	// ALICE adds a resource of base 0 to NFT [0,0]
+	assert_ok!(RmrkCore::add_resource(
+		Origin::signed(ALICE),
+		COLLECTION_ID_0,
+		NFT_ID_0,
+		bvec![0u8; 3],
+		Some(0),
+		None,
+		None,
+		None,
+		None,
+		None,
+		Some(vec![201]),
+	));
+	// ALICE adds a resource for slot 201 of base 0 to NFT [0,1]
+	assert_ok!(RmrkCore::add_resource(
+		Origin::signed(ALICE),
+		COLLECTION_ID_0,
+		NFT_ID_1,
+		bvec![1u8; 3],
+		Some(0),
+		None,
+		None,
+		Some(201),
+		None,
+		None,
+		None,
+	));
+	// ALICE sends NFT [0,1] to NFT [0,0] and equips it
+	assert_ok!(RmrkCore::send(
+		Origin::signed(ALICE),
+		COLLECTION_ID_0,
+		NFT_ID_1,
+		AccountIdOrCollectionNftTuple::CollectionAndNftTuple(COLLECTION_ID_0, NFT_ID_0),
+	));
+	assert_ok!(RmrkEquip::equip(
+		Origin::signed(ALICE),
+		(COLLECTION_ID_0, NFT_ID_1),
+		(COLLECTION_ID_0, NFT_ID_0),
+		0,
+		201,
+	));
+}
+
+/// Payload signed by the issuer of a mint voucher
+fn voucher_payload(voucher: &MintVoucherOf<Test>) -> Vec<u8> {
+	(b"rmrk/voucher", System::block_hash(0), voucher).encode()
+}
+
+/// Payload signed by the maker of an order
+fn order_payload(order: &OrderOf<Test>) -> Vec<u8> {
+	(b"rmrk/order", System::block_hash(0), order).encode()
+}
+
+/// Runs the market hooks of every block up to and including block `n`
+fn run_to_block(n: u64) {
+	while System::block_number() < n {
+		System::set_block_number(System::block_number() + 1);
+		RmrkMarket::on_initialize(System::block_number());
+	}
+}
+
 #[test]
 fn list_works() {
 	new_test_ext().execute_with(|| {
@@ -59,7 +140,7 @@ fn list_works() {
 		assert_eq!(RmrkCore::collections(COLLECTION_ID_0).unwrap().nfts_count, 2);
 		// BOB shouldn't be able to list ALICE's NFT
 		assert_noop!(
-			RmrkMarket::list(Origin::signed(BOB), COLLECTION_ID_0, NFT_ID_0, 10u128, None,),
+			RmrkMarket::list(Origin::signed(BOB), COLLECTION_ID_0, NFT_ID_0, 10u128, None, None,),
 			Error::<Test>::NoPermission
 		);
 		// ALICE cannot list a non-existing NFT
@@ -70,6 +151,7 @@ fn list_works() {
 				NOT_EXISTING_NFT_ID,
 				10u128,
 				None,
+				None,
 			),
 			Error::<Test>::TokenDoesNotExist
 		);
@@ -91,10 +173,10 @@ fn list_works() {
 			nft_id: NFT_ID_1,
 			approval_required: false,
 		}));
-		// ALICE cannot list NFT [0,1] bc it is owned by NFT[0,0]
+		// BOB cannot list NFT [0,1] bc ALICE is its root owner
 		assert_noop!(
-			RmrkMarket::list(Origin::signed(ALICE), COLLECTION_ID_0, NFT_ID_1, 10u128, None,),
-			Error::<Test>::CannotListNftOwnedByNft
+			RmrkMarket::list(Origin::signed(BOB), COLLECTION_ID_0, NFT_ID_1, 10u128, None, None,),
+			Error::<Test>::NoPermission
 		);
 		// ALICE lists the NFT successfully
 		assert_ok!(RmrkMarket::list(
@@ -103,6 +185,7 @@ fn list_works() {
 			NFT_ID_0,
 			10u128,
 			None,
+			None,
 		));
 		// Listed NFT should trigger TokenListed event
 		System::assert_last_event(MockEvent::RmrkMarket(crate::Event::TokenListed {
@@ -110,6 +193,7 @@ fn list_works() {
 			collection_id: 0,
 			nft_id: 0,
 			price: 10u128,
+			asset_id: None,
 		}));
 	});
 }
@@ -132,6 +216,7 @@ fn buy_works() {
 			NFT_ID_0,
 			10u128,
 			None,
+			None,
 		));
 		// Listed NFT should trigger TokenListed event
 		System::assert_last_event(MockEvent::RmrkMarket(crate::Event::TokenListed {
@@ -139,19 +224,26 @@ fn buy_works() {
 			collection_id: COLLECTION_ID_0,
 			nft_id: NFT_ID_0,
 			price: 10u128,
+			asset_id: None,
 		}));
 		// Ensure that ALICE cannot buy the listed NFT
 		assert_noop!(
-			RmrkMarket::buy(Origin::signed(ALICE), COLLECTION_ID_0, NFT_ID_0, Some(10u128),),
+			RmrkMarket::buy(Origin::signed(ALICE), COLLECTION_ID_0, NFT_ID_0, Some(10u128), None),
 			Error::<Test>::CannotBuyOwnToken
 		);
 		// Ensure that ALICE cannot buy the listed NFT
 		assert_noop!(
-			RmrkMarket::buy(Origin::signed(BOB), COLLECTION_ID_0, NFT_ID_0, Some(9u128),),
+			RmrkMarket::buy(Origin::signed(BOB), COLLECTION_ID_0, NFT_ID_0, Some(9u128), None),
 			Error::<Test>::PriceDiffersFromExpected
 		);
 		// BOB buys the NFT and the NFT is transferred from ALICE to BOB
-		assert_ok!(RmrkMarket::buy(Origin::signed(BOB), COLLECTION_ID_0, NFT_ID_0, Some(10u128),));
+		assert_ok!(RmrkMarket::buy(
+			Origin::signed(BOB),
+			COLLECTION_ID_0,
+			NFT_ID_0,
+			Some(10u128),
+			None
+		));
 		// Bought NFT should trigger TokenSold event
 		System::assert_last_event(MockEvent::RmrkMarket(crate::Event::TokenSold {
 			owner: ALICE,
@@ -165,6 +257,272 @@ fn buy_works() {
 	});
 }
 
+#[test]
+fn buy_pays_royalty_works() {
+	new_test_ext().execute_with(|| {
+		// Create a basic collection
+		assert_ok!(basic_collection());
+		// Mint an NFT with CHARLIE as royalty recipient of 1.525%
+		assert_ok!(RmrkCore::mint_nft(
+			Origin::signed(ALICE),
+			ALICE,
+			COLLECTION_ID_0,
+			Some(CHARLIE),
+			Some(Permill::from_parts(15_250)),
+			bvec![0u8; 20],
+		));
+		// ALICE lists the NFT at 1_001
+		assert_ok!(RmrkMarket::list(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			NFT_ID_0,
+			1_001u128,
+			None,
+			None,
+		));
+		let alice_balance = Balances::free_balance(ALICE);
+		let bob_balance = Balances::free_balance(BOB);
+		let charlie_balance = Balances::free_balance(CHARLIE);
+		// BOB buys the NFT
+		assert_ok!(RmrkMarket::buy(Origin::signed(BOB), COLLECTION_ID_0, NFT_ID_0, None, None));
+		// Royalty of 15.265 is rounded down to 15 and paid to CHARLIE
+		System::assert_has_event(MockEvent::RmrkMarket(crate::Event::RoyaltyPaid {
+			recipient: CHARLIE,
+			collection_id: COLLECTION_ID_0,
+			nft_id: NFT_ID_0,
+			amount: 15u128,
+		}));
+		assert_eq!(Balances::free_balance(CHARLIE), charlie_balance + 15);
+		// ALICE receives the rest of the price
+		assert_eq!(Balances::free_balance(ALICE), alice_balance + 986);
+		// BOB pays the full price
+		assert_eq!(Balances::free_balance(BOB), bob_balance - 1_001);
+		System::assert_last_event(MockEvent::RmrkMarket(crate::Event::TokenSold {
+			owner: ALICE,
+			buyer: BOB,
+			collection_id: COLLECTION_ID_0,
+			nft_id: NFT_ID_0,
+			price: 1_001u128,
+		}));
+		// BOB lists the NFT at 10, royalty rounds down to 0
+		assert_ok!(RmrkMarket::list(
+			Origin::signed(BOB),
+			COLLECTION_ID_0,
+			NFT_ID_0,
+			10u128,
+			None,
+			None,
+		));
+		let bob_balance = Balances::free_balance(BOB);
+		let charlie_balance = Balances::free_balance(CHARLIE);
+		// ALICE buys the NFT back
+		assert_ok!(RmrkMarket::buy(Origin::signed(ALICE), COLLECTION_ID_0, NFT_ID_0, None, None));
+		// No royalty is paid and BOB receives the full price
+		assert_eq!(Balances::free_balance(CHARLIE), charlie_balance);
+		assert_eq!(Balances::free_balance(BOB), bob_balance + 10);
+	});
+}
+
+#[test]
+fn royalty_below_existential_deposit_is_skipped() {
+	new_test_ext().execute_with(|| {
+		ExistentialDeposit::set(&500);
+		// Create a basic collection
+		assert_ok!(basic_collection());
+		// Mint an NFT with DAVE, who has no account yet, as royalty recipient of 10%
+		assert_ok!(RmrkCore::mint_nft(
+			Origin::signed(ALICE),
+			ALICE,
+			COLLECTION_ID_0,
+			Some(DAVE),
+			Some(Permill::from_percent(10)),
+			bvec![0u8; 20],
+		));
+		// ALICE lists the NFT at 1_000
+		assert_ok!(RmrkMarket::list(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			NFT_ID_0,
+			1_000u128,
+			None,
+			None,
+		));
+		let alice_balance = Balances::free_balance(ALICE);
+		// BOB buys the NFT, the royalty of 100 cannot create DAVE's account
+		assert_ok!(RmrkMarket::buy(Origin::signed(BOB), COLLECTION_ID_0, NFT_ID_0, None, None));
+		// No royalty is paid and ALICE receives the full price
+		assert_eq!(Balances::total_balance(&DAVE), 0);
+		assert_eq!(Balances::free_balance(ALICE), alice_balance + 1_000);
+		// BOB lists the NFT at 10_000
+		assert_ok!(RmrkMarket::list(
+			Origin::signed(BOB),
+			COLLECTION_ID_0,
+			NFT_ID_0,
+			10_000u128,
+			None,
+			None,
+		));
+		// ALICE buys the NFT back, the royalty of 1_000 reaches the existential deposit
+		assert_ok!(RmrkMarket::buy(Origin::signed(ALICE), COLLECTION_ID_0, NFT_ID_0, None, None));
+		System::assert_has_event(MockEvent::RmrkMarket(crate::Event::RoyaltyPaid {
+			recipient: DAVE,
+			collection_id: COLLECTION_ID_0,
+			nft_id: NFT_ID_0,
+			amount: 1_000u128,
+		}));
+		assert_eq!(Balances::free_balance(DAVE), 1_000);
+	});
+}
+
+#[test]
+fn accept_offer_pays_royalty_works() {
+	new_test_ext().execute_with(|| {
+		// Create a basic collection
+		assert_ok!(basic_collection());
+		// Mint an NFT with CHARLIE as royalty recipient of 10%
+		assert_ok!(RmrkCore::mint_nft(
+			Origin::signed(ALICE),
+			ALICE,
+			COLLECTION_ID_0,
+			Some(CHARLIE),
+			Some(Permill::from_percent(10)),
+			bvec![0u8; 20],
+		));
+		// BOB places an offer on the NFT
+		assert_ok!(RmrkMarket::make_offer(
+			Origin::signed(BOB),
+			COLLECTION_ID_0,
+			NFT_ID_0,
+			MIN_OFFER_ON_NFT,
+			None,
+			None,
+			None,
+		));
+		let alice_balance = Balances::free_balance(ALICE);
+		let charlie_balance = Balances::free_balance(CHARLIE);
+		// ALICE accepts BOB's offer
+		assert_ok!(RmrkMarket::accept_offer(Origin::signed(ALICE), COLLECTION_ID_0, NFT_ID_0, BOB));
+		// CHARLIE receives 10% of the offer, ALICE the remaining 90%
+		System::assert_has_event(MockEvent::RmrkMarket(crate::Event::RoyaltyPaid {
+			recipient: CHARLIE,
+			collection_id: COLLECTION_ID_0,
+			nft_id: NFT_ID_0,
+			amount: MIN_OFFER_ON_NFT / 10,
+		}));
+		assert_eq!(Balances::free_balance(CHARLIE), charlie_balance + MIN_OFFER_ON_NFT / 10);
+		assert_eq!(Balances::free_balance(ALICE), alice_balance + MIN_OFFER_ON_NFT / 10 * 9);
+		// BOB's reserved offer is fully spent
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+	});
+}
+
+#[test]
+fn buy_pays_marketplace_fee_works() {
+	new_test_ext().execute_with(|| {
+		// Only the ProtocolOrigin can set the marketplace fee
+		assert_noop!(
+			RmrkMarket::set_fee(Origin::signed(ALICE), Permill::from_percent(2)),
+			BadOrigin
+		);
+		// Root sets the marketplace fee to 2%
+		assert_ok!(RmrkMarket::set_fee(Origin::root(), Permill::from_percent(2)));
+		System::assert_last_event(MockEvent::RmrkMarket(crate::Event::MarketplaceFeeSet {
+			fee: Permill::from_percent(2),
+		}));
+		assert_eq!(RmrkMarket::marketplace_fee(), Permill::from_percent(2));
+		// Create a basic collection
+		assert_ok!(basic_collection());
+		// Mint an NFT with CHARLIE as royalty recipient of 10%
+		assert_ok!(RmrkCore::mint_nft(
+			Origin::signed(ALICE),
+			ALICE,
+			COLLECTION_ID_0,
+			Some(CHARLIE),
+			Some(Permill::from_percent(10)),
+			bvec![0u8; 20],
+		));
+		// ALICE lists the NFT at 1_000
+		assert_ok!(RmrkMarket::list(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			NFT_ID_0,
+			1_000u128,
+			None,
+			None,
+		));
+		let alice_balance = Balances::free_balance(ALICE);
+		let charlie_balance = Balances::free_balance(CHARLIE);
+		// BOB buys the NFT
+		assert_ok!(RmrkMarket::buy(Origin::signed(BOB), COLLECTION_ID_0, NFT_ID_0, None, None));
+		// Fee account receives 2% of the price
+		System::assert_has_event(MockEvent::RmrkMarket(crate::Event::MarketplaceFeePaid {
+			collection_id: COLLECTION_ID_0,
+			nft_id: NFT_ID_0,
+			amount: 20u128,
+		}));
+		assert_eq!(Balances::free_balance(FEE_ACCOUNT), 20);
+		// CHARLIE receives 10% royalty, ALICE receives the rest
+		assert_eq!(Balances::free_balance(CHARLIE), charlie_balance + 100);
+		assert_eq!(Balances::free_balance(ALICE), alice_balance + 880);
+	});
+}
+
+#[test]
+fn fee_below_existential_deposit_is_skipped() {
+	new_test_ext().execute_with(|| {
+		ExistentialDeposit::set(&500);
+		// The marketplace fee defaults to 2%
+		DefaultMarketplaceFee::set(&Permill::from_percent(2));
+		assert_eq!(RmrkMarket::marketplace_fee(), Permill::from_percent(2));
+		// Create a basic collection
+		assert_ok!(basic_collection());
+		// Mint an NFT without royalty
+		assert_ok!(RmrkCore::mint_nft(
+			Origin::signed(ALICE),
+			ALICE,
+			COLLECTION_ID_0,
+			None,
+			None,
+			bvec![0u8; 20],
+		));
+		// ALICE lists the NFT at 1_000
+		assert_ok!(RmrkMarket::list(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			NFT_ID_0,
+			1_000u128,
+			None,
+			None,
+		));
+		let alice_balance = Balances::free_balance(ALICE);
+		// BOB buys the NFT, the fee of 20 cannot create the fee account
+		assert_ok!(RmrkMarket::buy(Origin::signed(BOB), COLLECTION_ID_0, NFT_ID_0, None, None));
+		// No fee is paid and ALICE receives the full price
+		assert_eq!(Balances::total_balance(&FEE_ACCOUNT), 0);
+		assert_eq!(Balances::free_balance(ALICE), alice_balance + 1_000);
+		// BOB lists the NFT at 100_000
+		assert_ok!(RmrkMarket::list(
+			Origin::signed(BOB),
+			COLLECTION_ID_0,
+			NFT_ID_0,
+			100_000u128,
+			None,
+			None,
+		));
+		let bob_balance = Balances::free_balance(BOB);
+		// ALICE buys the NFT back, the fee of 2_000 reaches the existential deposit
+		assert_ok!(RmrkMarket::buy(Origin::signed(ALICE), COLLECTION_ID_0, NFT_ID_0, None, None));
+		System::assert_has_event(MockEvent::RmrkMarket(crate::Event::MarketplaceFeePaid {
+			collection_id: COLLECTION_ID_0,
+			nft_id: NFT_ID_0,
+			amount: 2_000u128,
+		}));
+		assert_eq!(Balances::free_balance(FEE_ACCOUNT), 2_000);
+		// BOB receives the rest of the price
+		assert_eq!(Balances::free_balance(BOB), bob_balance + 98_000);
+	});
+}
+
 #[test]
 fn buy_wont_work_after_list_expires() {
 	new_test_ext().execute_with(|| {
@@ -183,6 +541,7 @@ fn buy_wont_work_after_list_expires() {
 			NFT_ID_0,
 			10u128,
 			Some(1),
+			None,
 		));
 		// Listed NFT should trigger TokenListed event
 		System::assert_last_event(MockEvent::RmrkMarket(crate::Event::TokenListed {
@@ -190,12 +549,13 @@ fn buy_wont_work_after_list_expires() {
 			collection_id: COLLECTION_ID_0,
 			nft_id: NFT_ID_0,
 			price: 10u128,
+			asset_id: None,
 		}));
 		// Set block number to expired block
 		System::set_block_number(2);
 		// Ensure that BOB cannot buy the listed NFT as the listing expired
 		assert_noop!(
-			RmrkMarket::buy(Origin::signed(BOB), COLLECTION_ID_0, NFT_ID_0, None,),
+			RmrkMarket::buy(Origin::signed(BOB), COLLECTION_ID_0, NFT_ID_0, None, None),
 			Error::<Test>::ListingHasExpired
 		);
 	});
@@ -219,6 +579,7 @@ fn send_wont_work_if_sent_after_list() {
 			NFT_ID_0,
 			10u128,
 			None,
+			None,
 		));
 		// Listed NFT should trigger TokenListed event
 		System::assert_last_event(MockEvent::RmrkMarket(crate::Event::TokenListed {
@@ -226,16 +587,31 @@ fn send_wont_work_if_sent_after_list() {
 			collection_id: COLLECTION_ID_0,
 			nft_id: NFT_ID_0,
 			price: 10u128,
+			asset_id: None,
 		}));
 		// Ensure that ALICE cannot buy the listed NFT
 		assert_noop!(
-			RmrkMarket::buy(Origin::signed(ALICE), COLLECTION_ID_0, NFT_ID_0, None,),
+			RmrkMarket::buy(Origin::signed(ALICE), COLLECTION_ID_0, NFT_ID_0, None, None),
 			Error::<Test>::CannotBuyOwnToken
 		);
-		// TODO: NFT Lock Tests Ensure ALICE cannot sends CHARLIE NFT [0,0] bc it is now locked
+		// ALICE cannot send NFT [0,0] to CHARLIE bc it is now locked
+		assert_noop!(
+			RmrkCore::send(
+				Origin::signed(ALICE),
+				COLLECTION_ID_0,
+				NFT_ID_0,
+				AccountIdOrCollectionNftTuple::AccountId(CHARLIE),
+			),
+			pallet_rmrk_core::Error::<Test>::NftIsLocked
+		);
+		// ALICE cannot burn NFT [0,0] bc it is now locked
+		assert_noop!(
+			RmrkCore::burn_nft(Origin::signed(ALICE), COLLECTION_ID_0, NFT_ID_0),
+			pallet_rmrk_core::Error::<Test>::NftIsLocked
+		);
 		// BOB buys the NFT at whatever price is in storage and the NFT is transferred from ALICE to
 		// BOB
-		assert_ok!(RmrkMarket::buy(Origin::signed(BOB), COLLECTION_ID_0, NFT_ID_0, None,));
+		assert_ok!(RmrkMarket::buy(Origin::signed(BOB), COLLECTION_ID_0, NFT_ID_0, None, None));
 		// Bought NFT should trigger TokenSold event
 		System::assert_last_event(MockEvent::RmrkMarket(crate::Event::TokenSold {
 			owner: ALICE,
@@ -268,6 +644,7 @@ fn send_to_nft_wont_work_after_list() {
 			NFT_ID_0,
 			10u128,
 			None,
+			None,
 		));
 		// Listed NFT should trigger TokenListed event
 		System::assert_last_event(MockEvent::RmrkMarket(crate::Event::TokenListed {
@@ -275,15 +652,31 @@ fn send_to_nft_wont_work_after_list() {
 			collection_id: COLLECTION_ID_0,
 			nft_id: NFT_ID_0,
 			price: 10u128,
+			asset_id: None,
 		}));
 		// Ensure that ALICE cannot buy the listed NFT
 		assert_noop!(
-			RmrkMarket::buy(Origin::signed(ALICE), COLLECTION_ID_0, NFT_ID_0, Some(10u128),),
+			RmrkMarket::buy(Origin::signed(ALICE), COLLECTION_ID_0, NFT_ID_0, Some(10u128), None),
 			Error::<Test>::CannotBuyOwnToken
 		);
-		// TODO: NFT Lock Tests ALICE sends NFT [0,0] to NFT [0,1] won't work
+		// ALICE cannot send NFT [0,0] to NFT [0,1] bc it is now locked
+		assert_noop!(
+			RmrkCore::send(
+				Origin::signed(ALICE),
+				COLLECTION_ID_0,
+				NFT_ID_0,
+				AccountIdOrCollectionNftTuple::CollectionAndNftTuple(COLLECTION_ID_0, NFT_ID_1),
+			),
+			pallet_rmrk_core::Error::<Test>::NftIsLocked
+		);
 		// BOB buys the NFT and the NFT is transferred from ALICE to BOB
-		assert_ok!(RmrkMarket::buy(Origin::signed(BOB), COLLECTION_ID_0, NFT_ID_0, Some(10u128),));
+		assert_ok!(RmrkMarket::buy(
+			Origin::signed(BOB),
+			COLLECTION_ID_0,
+			NFT_ID_0,
+			Some(10u128),
+			None
+		));
 		// Bought NFT should trigger TokenSold event
 		System::assert_last_event(MockEvent::RmrkMarket(crate::Event::TokenSold {
 			owner: ALICE,
@@ -334,6 +727,8 @@ fn accept_offer_wont_work_if_traded_to_nft_after_list() {
 			NFT_ID_0,
 			MIN_OFFER_ON_NFT,
 			None,
+			None,
+			None,
 		));
 		// Offer from BOB on ALICE's NFT should trigger OfferPlaced event
 		System::assert_last_event(MockEvent::RmrkMarket(crate::Event::OfferPlaced {
@@ -341,6 +736,7 @@ fn accept_offer_wont_work_if_traded_to_nft_after_list() {
 			collection_id: COLLECTION_ID_0,
 			nft_id: NFT_ID_0,
 			price: MIN_OFFER_ON_NFT,
+			asset_id: None,
 		}));
 		// ALICE sends NFT [0,0] to NFT [0.1]
 		assert_ok!(RmrkCore::send(
@@ -362,9 +758,12 @@ fn accept_offer_wont_work_if_traded_to_nft_after_list() {
 		}));
 		// ALICE cannot accept offer anymore
 		assert_noop!(
-			RmrkMarket::accept_offer(Origin::signed(ALICE), COLLECTION_ID_0, NFT_ID_0, BOB,),
+			RmrkMarket::accept_offer(Origin::signed(ALICE), COLLECTION_ID_0, NFT_ID_0, BOB),
 			Error::<Test>::NoPermission
 		);
+		// BOB's offer was refunded when the NFT was sent
+		assert!(RmrkMarket::offers((COLLECTION_ID_0, NFT_ID_0), BOB).is_none());
+		assert_eq!(Balances::reserved_balance(BOB), 0);
 	});
 }
 
@@ -383,7 +782,7 @@ fn unlist_works() {
 		assert_eq!(RmrkCore::collections(COLLECTION_ID_0).unwrap().nfts_count, 2);
 		// BOB shouldn't be able to list ALICE's NFT
 		assert_noop!(
-			RmrkMarket::list(Origin::signed(BOB), COLLECTION_ID_0, NFT_ID_0, 10u128, None,),
+			RmrkMarket::list(Origin::signed(BOB), COLLECTION_ID_0, NFT_ID_0, 10u128, None, None,),
 			Error::<Test>::NoPermission
 		);
 		// ALICE cannot list a non-existing NFT
@@ -394,6 +793,7 @@ fn unlist_works() {
 				NOT_EXISTING_NFT_ID,
 				10u128,
 				None,
+				None,
 			),
 			Error::<Test>::TokenDoesNotExist
 		);
@@ -409,6 +809,7 @@ fn unlist_works() {
 			NFT_ID_0,
 			10u128,
 			None,
+			None,
 		));
 		// Listed NFT should trigger TokenListed event
 		System::assert_last_event(MockEvent::RmrkMarket(crate::Event::TokenListed {
@@ -416,6 +817,7 @@ fn unlist_works() {
 			collection_id: COLLECTION_ID_0,
 			nft_id: NFT_ID_0,
 			price: 10u128,
+			asset_id: None,
 		}));
 		// BOB cannot unlist a NFT if not owned by BOB
 		assert_noop!(
@@ -430,6 +832,13 @@ fn unlist_works() {
 			collection_id: COLLECTION_ID_0,
 			nft_id: NFT_ID_0,
 		}));
+		// Unlisted NFT is no longer locked and can be sent
+		assert_ok!(RmrkCore::send(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			NFT_ID_0,
+			AccountIdOrCollectionNftTuple::AccountId(BOB),
+		));
 	});
 }
 
@@ -448,6 +857,8 @@ fn offer_works() {
 				NFT_ID_0,
 				MIN_OFFER_ON_NFT,
 				None,
+				None,
+				None,
 			),
 			Error::<Test>::TokenDoesNotExist
 		);
@@ -463,6 +874,8 @@ fn offer_works() {
 				NFT_ID_0,
 				MIN_OFFER_ON_NFT,
 				None,
+				None,
+				None,
 			),
 			Error::<Test>::CannotOfferOnOwnToken
 		);
@@ -474,6 +887,8 @@ fn offer_works() {
 				NFT_ID_0,
 				MIN_OFFER_ON_NFT - 1,
 				None,
+				None,
+				None,
 			),
 			Error::<Test>::OfferTooLow
 		);
@@ -484,6 +899,8 @@ fn offer_works() {
 			NFT_ID_0,
 			MIN_OFFER_ON_NFT,
 			None,
+			None,
+			None,
 		));
 		// Offer from BOB on ALICE's NFT should trigger OfferPlaced event
 		System::assert_last_event(MockEvent::RmrkMarket(crate::Event::OfferPlaced {
@@ -491,6 +908,7 @@ fn offer_works() {
 			collection_id: COLLECTION_ID_0,
 			nft_id: NFT_ID_0,
 			price: MIN_OFFER_ON_NFT,
+			asset_id: None,
 		}));
 		// BOB cannot offer again on a NFT with an active offer
 		assert_noop!(
@@ -500,12 +918,72 @@ fn offer_works() {
 				NFT_ID_0,
 				MIN_OFFER_ON_NFT + 50,
 				None,
+				None,
+				None,
 			),
 			Error::<Test>::AlreadyOffered
 		);
 	});
 }
 
+#[test]
+fn offers_per_nft_are_bounded() {
+	new_test_ext().execute_with(|| {
+		// Create a basic collection
+		assert_ok!(basic_collection());
+		// Mint an NFT
+		assert_ok!(basic_mint());
+		Balances::make_free_balance_be(&DAVE, 100_000 * UNITS);
+		// BOB and CHARLIE make the maximum of 2 offers on the NFT
+		for offerer in [BOB, CHARLIE] {
+			assert_ok!(RmrkMarket::make_offer(
+				Origin::signed(offerer),
+				COLLECTION_ID_0,
+				NFT_ID_0,
+				MIN_OFFER_ON_NFT,
+				None,
+				None,
+				None,
+			));
+		}
+		assert_eq!(RmrkMarket::offers_count((COLLECTION_ID_0, NFT_ID_0)), 2);
+		// DAVE cannot make a third offer
+		assert_noop!(
+			RmrkMarket::make_offer(
+				Origin::signed(DAVE),
+				COLLECTION_ID_0,
+				NFT_ID_0,
+				MIN_OFFER_ON_NFT,
+				None,
+				None,
+				None,
+			),
+			Error::<Test>::TooManyOffers
+		);
+		// Once BOB withdraws the offer, DAVE can make an offer
+		assert_ok!(RmrkMarket::withdraw_offer(Origin::signed(BOB), COLLECTION_ID_0, NFT_ID_0));
+		assert_eq!(RmrkMarket::offers_count((COLLECTION_ID_0, NFT_ID_0)), 1);
+		assert_ok!(RmrkMarket::make_offer(
+			Origin::signed(DAVE),
+			COLLECTION_ID_0,
+			NFT_ID_0,
+			MIN_OFFER_ON_NFT,
+			None,
+			None,
+			None,
+		));
+		// Sending the NFT refunds all offers on it
+		assert_ok!(RmrkCore::send(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			NFT_ID_0,
+			AccountIdOrCollectionNftTuple::AccountId(BOB),
+		));
+		assert_eq!(RmrkMarket::offers_count((COLLECTION_ID_0, NFT_ID_0)), 0);
+		assert_eq!(Balances::reserved_balance(DAVE), 0);
+	});
+}
+
 #[test]
 fn offer_withdrawn_works() {
 	new_test_ext().execute_with(|| {
@@ -521,6 +999,8 @@ fn offer_withdrawn_works() {
 				NFT_ID_0,
 				MIN_OFFER_ON_NFT,
 				None,
+				None,
+				None,
 			),
 			Error::<Test>::TokenDoesNotExist
 		);
@@ -536,6 +1016,8 @@ fn offer_withdrawn_works() {
 				NFT_ID_0,
 				MIN_OFFER_ON_NFT,
 				None,
+				None,
+				None,
 			),
 			Error::<Test>::CannotOfferOnOwnToken
 		);
@@ -547,6 +1029,8 @@ fn offer_withdrawn_works() {
 				NFT_ID_0,
 				MIN_OFFER_ON_NFT - 1,
 				None,
+				None,
+				None,
 			),
 			Error::<Test>::OfferTooLow
 		);
@@ -562,6 +1046,8 @@ fn offer_withdrawn_works() {
 			NFT_ID_0,
 			MIN_OFFER_ON_NFT,
 			None,
+			None,
+			None,
 		));
 		// Offer from BOB on ALICE's NFT should trigger OfferPlaced event
 		System::assert_last_event(MockEvent::RmrkMarket(crate::Event::OfferPlaced {
@@ -569,6 +1055,7 @@ fn offer_withdrawn_works() {
 			collection_id: COLLECTION_ID_0,
 			nft_id: NFT_ID_0,
 			price: MIN_OFFER_ON_NFT,
+			asset_id: None,
 		}));
 		// ALICE cannot withdraw offer on own NFT
 		assert_noop!(
@@ -585,7 +1072,7 @@ fn offer_withdrawn_works() {
 		}));
 		// ALICE cannot accept offer anymore
 		assert_noop!(
-			RmrkMarket::accept_offer(Origin::signed(ALICE), COLLECTION_ID_0, NFT_ID_0, BOB,),
+			RmrkMarket::accept_offer(Origin::signed(ALICE), COLLECTION_ID_0, NFT_ID_0, BOB),
 			Error::<Test>::UnknownOffer
 		);
 	});
@@ -606,6 +1093,8 @@ fn accept_offer_works() {
 				NFT_ID_0,
 				MIN_OFFER_ON_NFT,
 				None,
+				None,
+				None,
 			),
 			Error::<Test>::TokenDoesNotExist
 		);
@@ -621,6 +1110,8 @@ fn accept_offer_works() {
 				NFT_ID_0,
 				MIN_OFFER_ON_NFT,
 				None,
+				None,
+				None,
 			),
 			Error::<Test>::CannotOfferOnOwnToken
 		);
@@ -632,6 +1123,8 @@ fn accept_offer_works() {
 				NFT_ID_0,
 				MIN_OFFER_ON_NFT - 1,
 				None,
+				None,
+				None,
 			),
 			Error::<Test>::OfferTooLow
 		);
@@ -642,6 +1135,8 @@ fn accept_offer_works() {
 			NFT_ID_0,
 			MIN_OFFER_ON_NFT,
 			None,
+			None,
+			None,
 		));
 		// Offer from BOB on ALICE's NFT should trigger OfferPlaced event
 		System::assert_last_event(MockEvent::RmrkMarket(crate::Event::OfferPlaced {
@@ -649,11 +1144,10 @@ fn accept_offer_works() {
 			collection_id: COLLECTION_ID_0,
 			nft_id: NFT_ID_0,
 			price: MIN_OFFER_ON_NFT,
+			asset_id: None,
 		}));
 		// ALICE accepts BOB's offer
-		assert_ok!(
-			RmrkMarket::accept_offer(Origin::signed(ALICE), COLLECTION_ID_0, NFT_ID_0, BOB,)
-		);
+		assert_ok!(RmrkMarket::accept_offer(Origin::signed(ALICE), COLLECTION_ID_0, NFT_ID_0, BOB));
 		// Offer from BOB on ALICE's NFT should trigger OfferPlaced event
 		System::assert_last_event(MockEvent::RmrkMarket(crate::Event::OfferAccepted {
 			owner: ALICE,
@@ -679,6 +1173,8 @@ fn accept_expired_offer_wont_works() {
 				NFT_ID_0,
 				MIN_OFFER_ON_NFT,
 				None,
+				None,
+				None,
 			),
 			Error::<Test>::TokenDoesNotExist
 		);
@@ -694,6 +1190,8 @@ fn accept_expired_offer_wont_works() {
 				NFT_ID_0,
 				MIN_OFFER_ON_NFT,
 				None,
+				None,
+				None,
 			),
 			Error::<Test>::CannotOfferOnOwnToken
 		);
@@ -705,6 +1203,8 @@ fn accept_expired_offer_wont_works() {
 				NFT_ID_0,
 				MIN_OFFER_ON_NFT - 1,
 				None,
+				None,
+				None,
 			),
 			Error::<Test>::OfferTooLow
 		);
@@ -715,6 +1215,8 @@ fn accept_expired_offer_wont_works() {
 			NFT_ID_0,
 			MIN_OFFER_ON_NFT,
 			Some(1),
+			None,
+			None,
 		));
 		// Offer from BOB on ALICE's NFT should trigger OfferPlaced event
 		System::assert_last_event(MockEvent::RmrkMarket(crate::Event::OfferPlaced {
@@ -722,18 +1224,2386 @@ fn accept_expired_offer_wont_works() {
 			collection_id: COLLECTION_ID_0,
 			nft_id: NFT_ID_0,
 			price: MIN_OFFER_ON_NFT,
+			asset_id: None,
 		}));
 		// CHARLIE cannot accepts BOB's offer
 		assert_noop!(
-			RmrkMarket::accept_offer(Origin::signed(CHARLIE), COLLECTION_ID_0, NFT_ID_0, BOB,),
+			RmrkMarket::accept_offer(Origin::signed(CHARLIE), COLLECTION_ID_0, NFT_ID_0, BOB),
 			Error::<Test>::NoPermission
 		);
 		// Set block number to expired block
 		System::set_block_number(2);
 		// ALICE accepts BOB's offer
 		assert_noop!(
-			RmrkMarket::accept_offer(Origin::signed(ALICE), COLLECTION_ID_0, NFT_ID_0, BOB,),
+			RmrkMarket::accept_offer(Origin::signed(ALICE), COLLECTION_ID_0, NFT_ID_0, BOB),
 			Error::<Test>::OfferHasExpired
 		);
 	});
 }
+
+#[test]
+fn english_auction_works() {
+	new_test_ext().execute_with(|| {
+		// Create a basic collection
+		assert_ok!(basic_collection());
+		// Mint an NFT
+		assert_ok!(basic_mint());
+		// BOB cannot put ALICE's NFT up for auction
+		assert_noop!(
+			RmrkMarket::create_auction(
+				Origin::signed(BOB),
+				COLLECTION_ID_0,
+				NFT_ID_0,
+				100,
+				10,
+				20,
+				None
+			),
+			Error::<Test>::NoPermission
+		);
+		// Auction must last at least a block
+		assert_noop!(
+			RmrkMarket::create_auction(
+				Origin::signed(ALICE),
+				COLLECTION_ID_0,
+				NFT_ID_0,
+				100,
+				10,
+				0,
+				None
+			),
+			Error::<Test>::InvalidAuctionDuration
+		);
+		// ALICE puts the NFT up for auction until block 21
+		assert_ok!(RmrkMarket::create_auction(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			NFT_ID_0,
+			100,
+			10,
+			20,
+			None
+		));
+		System::assert_last_event(MockEvent::RmrkMarket(crate::Event::AuctionCreated {
+			owner: ALICE,
+			collection_id: COLLECTION_ID_0,
+			nft_id: NFT_ID_0,
+			reserve_price: 100,
+			end: 21,
+			asset_id: None,
+		}));
+		// Auctioned NFT is locked
+		assert_noop!(
+			RmrkCore::send(
+				Origin::signed(ALICE),
+				COLLECTION_ID_0,
+				NFT_ID_0,
+				AccountIdOrCollectionNftTuple::AccountId(CHARLIE),
+			),
+			pallet_rmrk_core::Error::<Test>::NftIsLocked
+		);
+		// ALICE cannot bid on own NFT
+		assert_noop!(
+			RmrkMarket::bid(Origin::signed(ALICE), COLLECTION_ID_0, NFT_ID_0, 100),
+			Error::<Test>::CannotBuyOwnToken
+		);
+		// First bid must reach the reserve price
+		assert_noop!(
+			RmrkMarket::bid(Origin::signed(BOB), COLLECTION_ID_0, NFT_ID_0, 99),
+			Error::<Test>::BidTooLow
+		);
+		// BOB bids the reserve price
+		assert_ok!(RmrkMarket::bid(Origin::signed(BOB), COLLECTION_ID_0, NFT_ID_0, 100));
+		System::assert_last_event(MockEvent::RmrkMarket(crate::Event::BidPlaced {
+			bidder: BOB,
+			collection_id: COLLECTION_ID_0,
+			nft_id: NFT_ID_0,
+			amount: 100,
+		}));
+		assert_eq!(Balances::reserved_balance(BOB), 100);
+		// Next bid must exceed the highest bid by the minimum increment
+		assert_noop!(
+			RmrkMarket::bid(Origin::signed(CHARLIE), COLLECTION_ID_0, NFT_ID_0, 109),
+			Error::<Test>::BidTooLow
+		);
+		// CHARLIE outbids BOB, BOB is refunded
+		assert_ok!(RmrkMarket::bid(Origin::signed(CHARLIE), COLLECTION_ID_0, NFT_ID_0, 110));
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		assert_eq!(Balances::reserved_balance(CHARLIE), 110);
+		// BOB outbids CHARLIE at block 18, extending the auction to block 23
+		run_to_block(18);
+		assert_ok!(RmrkMarket::bid(Origin::signed(BOB), COLLECTION_ID_0, NFT_ID_0, 120));
+		System::assert_has_event(MockEvent::RmrkMarket(crate::Event::AuctionExtended {
+			collection_id: COLLECTION_ID_0,
+			nft_id: NFT_ID_0,
+			end: 23,
+		}));
+		assert_eq!(Balances::reserved_balance(CHARLIE), 0);
+		// Auction is not settled at its original end
+		run_to_block(22);
+		assert!(RmrkMarket::auctions(COLLECTION_ID_0, NFT_ID_0).is_some());
+		let alice_balance = Balances::free_balance(ALICE);
+		// Auction is settled at block 23 and BOB wins the NFT
+		run_to_block(23);
+		System::assert_last_event(MockEvent::RmrkMarket(crate::Event::AuctionSettled {
+			owner: ALICE,
+			winner: BOB,
+			collection_id: COLLECTION_ID_0,
+			nft_id: NFT_ID_0,
+			price: 120,
+		}));
+		assert!(RmrkMarket::auctions(COLLECTION_ID_0, NFT_ID_0).is_none());
+		assert_eq!(Uniques::owner(COLLECTION_ID_0, NFT_ID_0), Some(BOB));
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		assert_eq!(Balances::free_balance(ALICE), alice_balance + 120);
+		// Auction ended, bids are no longer possible
+		assert_noop!(
+			RmrkMarket::bid(Origin::signed(CHARLIE), COLLECTION_ID_0, NFT_ID_0, 200),
+			Error::<Test>::UnknownAuction
+		);
+	});
+}
+
+#[test]
+fn auction_is_cancelled_if_nft_moved() {
+	new_test_ext().execute_with(|| {
+		// Create a basic collection
+		assert_ok!(basic_collection());
+		// Mint an NFT
+		assert_ok!(basic_mint());
+		// ALICE puts the NFT up for auction until block 11
+		assert_ok!(RmrkMarket::create_auction(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			NFT_ID_0,
+			100,
+			10,
+			10,
+			None
+		));
+		// BOB bids on the NFT
+		assert_ok!(RmrkMarket::bid(Origin::signed(BOB), COLLECTION_ID_0, NFT_ID_0, 100));
+		// ALICE moves the NFT outside of RMRK
+		assert_ok!(Uniques::transfer(Origin::signed(ALICE), COLLECTION_ID_0, NFT_ID_0, CHARLIE));
+		// Auction is cancelled when it ends and BOB is refunded
+		run_to_block(11);
+		System::assert_last_event(MockEvent::RmrkMarket(crate::Event::AuctionCancelled {
+			collection_id: COLLECTION_ID_0,
+			nft_id: NFT_ID_0,
+		}));
+		assert_eq!(Uniques::owner(COLLECTION_ID_0, NFT_ID_0), Some(CHARLIE));
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		assert!(!RmrkCore::is_nft_locked(COLLECTION_ID_0, NFT_ID_0));
+	});
+}
+
+#[test]
+fn cancel_auction_works() {
+	new_test_ext().execute_with(|| {
+		// Create a basic collection
+		assert_ok!(basic_collection());
+		// Mint an NFT
+		assert_ok!(basic_mint());
+		// ALICE puts the NFT up for auction
+		assert_ok!(RmrkMarket::create_auction(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			NFT_ID_0,
+			100,
+			10,
+			10,
+			None
+		));
+		// Auctioned NFT cannot be listed
+		assert_noop!(
+			RmrkMarket::list(Origin::signed(ALICE), COLLECTION_ID_0, NFT_ID_0, 10u128, None, None),
+			pallet_rmrk_core::Error::<Test>::NftIsLocked
+		);
+		// BOB cannot cancel ALICE's auction
+		assert_noop!(
+			RmrkMarket::cancel_auction(Origin::signed(BOB), COLLECTION_ID_0, NFT_ID_0),
+			Error::<Test>::NoPermission
+		);
+		// ALICE cancels the auction
+		assert_ok!(RmrkMarket::cancel_auction(Origin::signed(ALICE), COLLECTION_ID_0, NFT_ID_0));
+		System::assert_last_event(MockEvent::RmrkMarket(crate::Event::AuctionCancelled {
+			collection_id: COLLECTION_ID_0,
+			nft_id: NFT_ID_0,
+		}));
+		assert!(RmrkMarket::auctions_ending_at(11).is_empty());
+		assert!(!RmrkCore::is_nft_locked(COLLECTION_ID_0, NFT_ID_0));
+		// ALICE puts the NFT up for auction again and BOB bids
+		assert_ok!(RmrkMarket::create_auction(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			NFT_ID_0,
+			100,
+			10,
+			10,
+			None
+		));
+		assert_ok!(RmrkMarket::bid(Origin::signed(BOB), COLLECTION_ID_0, NFT_ID_0, 100));
+		// Auction with bids cannot be cancelled
+		assert_noop!(
+			RmrkMarket::cancel_auction(Origin::signed(ALICE), COLLECTION_ID_0, NFT_ID_0),
+			Error::<Test>::AuctionHasBids
+		);
+	});
+}
+
+#[test]
+fn auctions_ending_in_same_block_are_bounded() {
+	new_test_ext().execute_with(|| {
+		// Create a basic collection
+		assert_ok!(basic_collection());
+		// Mint three NFTs
+		assert_ok!(basic_mint());
+		assert_ok!(basic_mint());
+		assert_ok!(basic_mint());
+		// Two auctions can end at block 11
+		assert_ok!(RmrkMarket::create_auction(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			0,
+			1,
+			1,
+			10,
+			None
+		));
+		assert_ok!(RmrkMarket::create_auction(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			1,
+			1,
+			1,
+			10,
+			None
+		));
+		// A third auction cannot end at block 11
+		assert_noop!(
+			RmrkMarket::create_auction(Origin::signed(ALICE), COLLECTION_ID_0, 2, 1, 1, 10, None),
+			Error::<Test>::TooManyAuctionsEnding
+		);
+	});
+}
+
+#[test]
+fn auction_ending_while_halted_is_settled_later() {
+	new_test_ext().execute_with(|| {
+		// Create a basic collection
+		assert_ok!(basic_collection());
+		// Mint an NFT
+		assert_ok!(basic_mint());
+		// ALICE puts the NFT up for auction until block 11
+		assert_ok!(RmrkMarket::create_auction(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			NFT_ID_0,
+			100,
+			10,
+			10,
+			None
+		));
+		// BOB bids the reserve price
+		assert_ok!(RmrkMarket::bid(Origin::signed(BOB), COLLECTION_ID_0, NFT_ID_0, 100));
+		// The auction cannot be settled before it ends
+		assert_noop!(
+			RmrkMarket::settle_auction(Origin::signed(CHARLIE), COLLECTION_ID_0, NFT_ID_0),
+			Error::<Test>::AuctionNotEnded
+		);
+		// The market is halted when the auction ends
+		assert_ok!(RmrkMarket::set_market_halted(Origin::root(), true));
+		run_to_block(11);
+		System::assert_last_event(MockEvent::RmrkMarket(crate::Event::AuctionSettlementDeferred {
+			collection_id: COLLECTION_ID_0,
+			nft_id: NFT_ID_0,
+		}));
+		// The NFT is not sold and BOB's bid stays reserved
+		assert!(RmrkMarket::auctions(COLLECTION_ID_0, NFT_ID_0).is_some());
+		assert_eq!(Uniques::owner(COLLECTION_ID_0, NFT_ID_0), Some(ALICE));
+		assert_eq!(Balances::reserved_balance(BOB), 100);
+		// Nobody can bid on the ended auction
+		assert_noop!(
+			RmrkMarket::bid(Origin::signed(CHARLIE), COLLECTION_ID_0, NFT_ID_0, 200),
+			Error::<Test>::MarketHalted
+		);
+		// The auction cannot be settled while the market is halted
+		assert_noop!(
+			RmrkMarket::settle_auction(Origin::signed(CHARLIE), COLLECTION_ID_0, NFT_ID_0),
+			Error::<Test>::MarketHalted
+		);
+		// Once the market is resumed, anyone can settle the auction
+		assert_ok!(RmrkMarket::set_market_halted(Origin::root(), false));
+		assert_noop!(
+			RmrkMarket::bid(Origin::signed(CHARLIE), COLLECTION_ID_0, NFT_ID_0, 200),
+			Error::<Test>::AuctionHasEnded
+		);
+		assert_ok!(RmrkMarket::settle_auction(Origin::signed(CHARLIE), COLLECTION_ID_0, NFT_ID_0));
+		System::assert_last_event(MockEvent::RmrkMarket(crate::Event::AuctionSettled {
+			owner: ALICE,
+			winner: BOB,
+			collection_id: COLLECTION_ID_0,
+			nft_id: NFT_ID_0,
+			price: 100,
+		}));
+		assert_eq!(Uniques::owner(COLLECTION_ID_0, NFT_ID_0), Some(BOB));
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		assert!(RmrkMarket::auctions(COLLECTION_ID_0, NFT_ID_0).is_none());
+	});
+}
+
+#[test]
+fn dutch_auction_works() {
+	new_test_ext().execute_with(|| {
+		// Create a basic collection
+		assert_ok!(basic_collection());
+		// Mint an NFT
+		assert_ok!(basic_mint());
+		// Dutch auction must end after it starts
+		assert_noop!(
+			RmrkMarket::list_dutch(
+				Origin::signed(ALICE),
+				COLLECTION_ID_0,
+				NFT_ID_0,
+				1_000,
+				100,
+				10,
+				10,
+				0,
+				None,
+				None
+			),
+			Error::<Test>::InvalidDutchAuction
+		);
+		// Floor price cannot exceed the start price
+		assert_noop!(
+			RmrkMarket::list_dutch(
+				Origin::signed(ALICE),
+				COLLECTION_ID_0,
+				NFT_ID_0,
+				100,
+				1_000,
+				10,
+				20,
+				0,
+				None,
+				None
+			),
+			Error::<Test>::InvalidDutchAuction
+		);
+		// ALICE lists the NFT declining from 1_000 at block 10 to 100 at block 20
+		assert_ok!(RmrkMarket::list_dutch(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			NFT_ID_0,
+			1_000,
+			100,
+			10,
+			20,
+			0,
+			None,
+			None
+		));
+		System::assert_last_event(MockEvent::RmrkMarket(crate::Event::TokenListedDutch {
+			owner: ALICE,
+			collection_id: COLLECTION_ID_0,
+			nft_id: NFT_ID_0,
+			start_price: 1_000,
+			floor_price: 100,
+			start: 10,
+			end: 20,
+			asset_id: None,
+		}));
+		// Price is 1_000 before the decline starts, above BOB's maximum price
+		assert_noop!(
+			RmrkMarket::buy(Origin::signed(BOB), COLLECTION_ID_0, NFT_ID_0, Some(999), None),
+			Error::<Test>::PriceAboveMaximum
+		);
+		// Price is 550 halfway through the decline
+		System::set_block_number(15);
+		assert_noop!(
+			RmrkMarket::buy(Origin::signed(BOB), COLLECTION_ID_0, NFT_ID_0, Some(549), None),
+			Error::<Test>::PriceAboveMaximum
+		);
+		let alice_balance = Balances::free_balance(ALICE);
+		// BOB buys the NFT at the current price
+		assert_ok!(RmrkMarket::buy(
+			Origin::signed(BOB),
+			COLLECTION_ID_0,
+			NFT_ID_0,
+			Some(600),
+			None
+		));
+		System::assert_last_event(MockEvent::RmrkMarket(crate::Event::TokenSold {
+			owner: ALICE,
+			buyer: BOB,
+			collection_id: COLLECTION_ID_0,
+			nft_id: NFT_ID_0,
+			price: 550,
+		}));
+		assert_eq!(Balances::free_balance(ALICE), alice_balance + 550);
+		assert_eq!(Uniques::owner(COLLECTION_ID_0, NFT_ID_0), Some(BOB));
+	});
+}
+
+#[test]
+fn dutch_auction_expires() {
+	new_test_ext().execute_with(|| {
+		// Create a basic collection
+		assert_ok!(basic_collection());
+		// Mint an NFT
+		assert_ok!(basic_mint());
+		// ALICE lists the NFT declining from 1_000 at block 10 to 100 at block 20, until block 15
+		assert_ok!(RmrkMarket::list_dutch(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			NFT_ID_0,
+			1_000,
+			100,
+			10,
+			20,
+			0,
+			Some(15),
+			None
+		));
+		assert_eq!(RmrkMarket::listed_nfts(COLLECTION_ID_0, NFT_ID_0).unwrap().expires, Some(15));
+		// BOB cannot buy the NFT after the listing expired
+		System::set_block_number(16);
+		assert_noop!(
+			RmrkMarket::buy(Origin::signed(BOB), COLLECTION_ID_0, NFT_ID_0, None, None),
+			Error::<Test>::ListingHasExpired
+		);
+	});
+}
+
+#[test]
+fn stepwise_dutch_auction_works() {
+	new_test_ext().execute_with(|| {
+		// Create a basic collection
+		assert_ok!(basic_collection());
+		// Mint two NFTs
+		assert_ok!(basic_mint());
+		assert_ok!(basic_mint());
+		// ALICE lists both NFTs declining from 1_000 at block 10 to 100 at block 20 every 4
+		// blocks
+		assert_ok!(RmrkMarket::list_dutch(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			NFT_ID_0,
+			1_000,
+			100,
+			10,
+			20,
+			4,
+			None,
+			None
+		));
+		assert_ok!(RmrkMarket::list_dutch(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			NFT_ID_1,
+			1_000,
+			100,
+			10,
+			20,
+			4,
+			None,
+			None
+		));
+		// At block 17 the price has dropped once, to 640
+		System::set_block_number(17);
+		assert_ok!(RmrkMarket::buy(Origin::signed(BOB), COLLECTION_ID_0, NFT_ID_0, None, None));
+		System::assert_last_event(MockEvent::RmrkMarket(crate::Event::TokenSold {
+			owner: ALICE,
+			buyer: BOB,
+			collection_id: COLLECTION_ID_0,
+			nft_id: NFT_ID_0,
+			price: 640,
+		}));
+		// After the end the price stays at the floor
+		System::set_block_number(25);
+		assert_ok!(RmrkMarket::buy(
+			Origin::signed(BOB),
+			COLLECTION_ID_0,
+			NFT_ID_1,
+			Some(100),
+			None
+		));
+		System::assert_last_event(MockEvent::RmrkMarket(crate::Event::TokenSold {
+			owner: ALICE,
+			buyer: BOB,
+			collection_id: COLLECTION_ID_0,
+			nft_id: NFT_ID_1,
+			price: 100,
+		}));
+	});
+}
+
+#[test]
+fn expired_listings_and_offers_are_swept() {
+	new_test_ext().execute_with(|| {
+		// Create a basic collection
+		assert_ok!(basic_collection());
+		// Mint three NFTs
+		assert_ok!(basic_mint());
+		assert_ok!(basic_mint());
+		assert_ok!(basic_mint());
+		// ALICE lists NFTs [0,0] and [0,2] until block 5 and NFT [0,1] without expiry
+		assert_ok!(RmrkMarket::list(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			0,
+			10u128,
+			Some(5),
+			None
+		));
+		assert_ok!(RmrkMarket::list(Origin::signed(ALICE), COLLECTION_ID_0, 1, 10u128, None, None));
+		assert_ok!(RmrkMarket::list(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			2,
+			10u128,
+			Some(5),
+			None
+		));
+		// BOB offers on NFT [0,0] until block 5, CHARLIE offers on NFT [0,1] without expiry
+		assert_ok!(RmrkMarket::make_offer(
+			Origin::signed(BOB),
+			COLLECTION_ID_0,
+			0,
+			MIN_OFFER_ON_NFT,
+			Some(5),
+			None,
+			None
+		));
+		assert_ok!(RmrkMarket::make_offer(
+			Origin::signed(CHARLIE),
+			COLLECTION_ID_0,
+			1,
+			MIN_OFFER_ON_NFT,
+			None,
+			None,
+			None
+		));
+		// At block 5 a sweep checks a single listing
+		System::set_block_number(5);
+		RmrkMarket::on_idle(5, u64::MAX);
+		assert!(ListedNfts::<Test>::iter().count() >= 2);
+		// The cursor carries the sweep over the next blocks
+		RmrkMarket::on_idle(5, u64::MAX);
+		RmrkMarket::on_idle(5, u64::MAX);
+		// Expired listings are removed and their NFTs unlocked
+		assert_eq!(ListedNfts::<Test>::iter().count(), 1);
+		assert!(RmrkMarket::listed_nfts(COLLECTION_ID_0, 1).is_some());
+		assert!(RmrkCore::locks(COLLECTION_ID_0, 0).is_none());
+		assert!(RmrkCore::locks(COLLECTION_ID_0, 2).is_none());
+		System::assert_has_event(MockEvent::RmrkMarket(crate::Event::ListingExpired {
+			owner: ALICE,
+			collection_id: COLLECTION_ID_0,
+			nft_id: 2,
+		}));
+		// Expired offer is removed and BOB's funds unreserved
+		assert_eq!(Offers::<Test>::iter().count(), 1);
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		assert_eq!(Balances::reserved_balance(CHARLIE), MIN_OFFER_ON_NFT);
+		System::assert_has_event(MockEvent::RmrkMarket(crate::Event::OfferExpired {
+			offerer: BOB,
+			collection_id: COLLECTION_ID_0,
+			nft_id: 0,
+		}));
+	});
+}
+
+#[test]
+fn expired_collection_offers_are_swept() {
+	new_test_ext().execute_with(|| {
+		// Create a basic collection
+		assert_ok!(basic_collection());
+		// BOB offers on the collection until block 5, CHARLIE without expiry
+		assert_ok!(RmrkMarket::make_collection_offer(
+			Origin::signed(BOB),
+			COLLECTION_ID_0,
+			MIN_OFFER_ON_NFT,
+			Some(5),
+			None,
+			None,
+		));
+		assert_ok!(RmrkMarket::make_collection_offer(
+			Origin::signed(CHARLIE),
+			COLLECTION_ID_0,
+			MIN_OFFER_ON_NFT,
+			None,
+			None,
+			None,
+		));
+		// The offer is kept before it expires
+		System::set_block_number(4);
+		RmrkMarket::on_idle(4, u64::MAX);
+		RmrkMarket::on_idle(4, u64::MAX);
+		assert_eq!(CollectionOffers::<Test>::iter().count(), 2);
+		// At block 5 the sweep removes the expired collection offer and unreserves BOB's funds
+		System::set_block_number(5);
+		RmrkMarket::on_idle(5, u64::MAX);
+		RmrkMarket::on_idle(5, u64::MAX);
+		assert!(RmrkMarket::collection_offers(COLLECTION_ID_0, BOB).is_none());
+		assert!(RmrkMarket::collection_offers(COLLECTION_ID_0, CHARLIE).is_some());
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		assert_eq!(Balances::reserved_balance(CHARLIE), MIN_OFFER_ON_NFT);
+		System::assert_has_event(MockEvent::RmrkMarket(crate::Event::CollectionOfferExpired {
+			offerer: BOB,
+			collection_id: COLLECTION_ID_0,
+		}));
+	});
+}
+
+#[test]
+fn offers_are_refunded_when_nft_sold_or_burned() {
+	new_test_ext().execute_with(|| {
+		// Create a basic collection
+		assert_ok!(basic_collection());
+		// Mint two NFTs
+		assert_ok!(basic_mint());
+		assert_ok!(basic_mint());
+		// BOB and CHARLIE place offers on NFT [0,0]
+		assert_ok!(RmrkMarket::make_offer(
+			Origin::signed(BOB),
+			COLLECTION_ID_0,
+			NFT_ID_0,
+			MIN_OFFER_ON_NFT,
+			None,
+			None,
+			None,
+		));
+		assert_ok!(RmrkMarket::make_offer(
+			Origin::signed(CHARLIE),
+			COLLECTION_ID_0,
+			NFT_ID_0,
+			MIN_OFFER_ON_NFT,
+			None,
+			None,
+			None,
+		));
+		// ALICE accepts BOB's offer
+		assert_ok!(RmrkMarket::accept_offer(Origin::signed(ALICE), COLLECTION_ID_0, NFT_ID_0, BOB));
+		// CHARLIE's offer is refunded
+		System::assert_has_event(MockEvent::RmrkMarket(crate::Event::OfferRefunded {
+			offerer: CHARLIE,
+			collection_id: COLLECTION_ID_0,
+			nft_id: NFT_ID_0,
+		}));
+		assert_eq!(Offers::<Test>::iter_prefix((COLLECTION_ID_0, NFT_ID_0)).count(), 0);
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		assert_eq!(Balances::reserved_balance(CHARLIE), 0);
+		// BOB places an offer on NFT [0,1]
+		assert_ok!(RmrkMarket::make_offer(
+			Origin::signed(BOB),
+			COLLECTION_ID_0,
+			NFT_ID_1,
+			MIN_OFFER_ON_NFT,
+			None,
+			None,
+			None,
+		));
+		assert_eq!(Balances::reserved_balance(BOB), MIN_OFFER_ON_NFT);
+		// ALICE burns NFT [0,1], BOB's offer is refunded
+		assert_ok!(RmrkCore::burn_nft(Origin::signed(ALICE), COLLECTION_ID_0, NFT_ID_1));
+		assert!(RmrkMarket::offers((COLLECTION_ID_0, NFT_ID_1), BOB).is_none());
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+	});
+}
+
+#[test]
+fn asset_listings_and_offers_work() {
+	new_test_ext().execute_with(|| {
+		// Create a sufficient asset and give BOB and CHARLIE some of it
+		assert_ok!(Assets::force_create(Origin::root(), ASSET_ID_0, ALICE, true, 1));
+		assert_ok!(Assets::mint(Origin::signed(ALICE), ASSET_ID_0, BOB, 1_000));
+		assert_ok!(Assets::mint(Origin::signed(ALICE), ASSET_ID_0, CHARLIE, 1_000));
+		// Create a basic collection
+		assert_ok!(basic_collection());
+		// Mint NFT
+		assert_ok!(basic_mint());
+		// ALICE lists the NFT for 100 of the asset
+		assert_ok!(RmrkMarket::list(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			NFT_ID_0,
+			100u128,
+			None,
+			Some(ASSET_ID_0),
+		));
+		System::assert_last_event(MockEvent::RmrkMarket(crate::Event::TokenListed {
+			owner: ALICE,
+			collection_id: COLLECTION_ID_0,
+			nft_id: NFT_ID_0,
+			price: 100u128,
+			asset_id: Some(ASSET_ID_0),
+		}));
+		// CHARLIE cannot offer nothing of the asset
+		assert_noop!(
+			RmrkMarket::make_offer(
+				Origin::signed(CHARLIE),
+				COLLECTION_ID_0,
+				NFT_ID_0,
+				0u128,
+				None,
+				Some(ASSET_ID_0),
+				None,
+			),
+			Error::<Test>::OfferTooLow
+		);
+		// CHARLIE offers 50 of the asset, which is moved to the asset escrow account
+		assert_ok!(RmrkMarket::make_offer(
+			Origin::signed(CHARLIE),
+			COLLECTION_ID_0,
+			NFT_ID_0,
+			50u128,
+			None,
+			Some(ASSET_ID_0),
+			None,
+		));
+		assert_eq!(Assets::balance(ASSET_ID_0, CHARLIE), 950);
+		assert_eq!(Assets::balance(ASSET_ID_0, RmrkMarket::asset_escrow_account()), 50);
+		// BOB buys the NFT with the asset, BOB's native balance is untouched
+		let bob_balance = Balances::free_balance(BOB);
+		assert_ok!(RmrkMarket::buy(
+			Origin::signed(BOB),
+			COLLECTION_ID_0,
+			NFT_ID_0,
+			Some(100u128),
+			None
+		));
+		assert_eq!(Assets::balance(ASSET_ID_0, BOB), 900);
+		assert_eq!(Assets::balance(ASSET_ID_0, ALICE), 100);
+		assert_eq!(Balances::free_balance(BOB), bob_balance);
+		// CHARLIE's offer was refunded from the asset escrow account
+		assert_eq!(Assets::balance(ASSET_ID_0, CHARLIE), 1_000);
+		assert_eq!(Assets::balance(ASSET_ID_0, RmrkMarket::asset_escrow_account()), 0);
+		// CHARLIE offers again and BOB accepts
+		assert_ok!(RmrkMarket::make_offer(
+			Origin::signed(CHARLIE),
+			COLLECTION_ID_0,
+			NFT_ID_0,
+			50u128,
+			None,
+			Some(ASSET_ID_0),
+			None,
+		));
+		assert_ok!(RmrkMarket::accept_offer(
+			Origin::signed(BOB),
+			COLLECTION_ID_0,
+			NFT_ID_0,
+			CHARLIE,
+		));
+		assert_eq!(Assets::balance(ASSET_ID_0, BOB), 950);
+		assert_eq!(Assets::balance(ASSET_ID_0, CHARLIE), 950);
+		assert_eq!(Assets::balance(ASSET_ID_0, RmrkMarket::asset_escrow_account()), 0);
+		assert_eq!(Uniques::owner(COLLECTION_ID_0, NFT_ID_0), Some(CHARLIE));
+	});
+}
+
+#[test]
+fn collection_offer_works() {
+	new_test_ext().execute_with(|| {
+		// Create a basic collection
+		assert_ok!(basic_collection());
+		// Mint two NFTs
+		assert_ok!(basic_mint());
+		assert_ok!(basic_mint());
+		// Cannot make a collection offer on a non-existing collection
+		assert_noop!(
+			RmrkMarket::make_collection_offer(
+				Origin::signed(BOB),
+				COLLECTION_ID_1,
+				MIN_OFFER_ON_NFT,
+				None,
+				None,
+				None,
+			),
+			Error::<Test>::CollectionDoesNotExist
+		);
+		// BOB offers on any NFT of the collection with the color red
+		assert_ok!(RmrkMarket::make_collection_offer(
+			Origin::signed(BOB),
+			COLLECTION_ID_0,
+			MIN_OFFER_ON_NFT,
+			None,
+			Some((stbk("color"), stb("red"))),
+			None,
+		));
+		System::assert_last_event(MockEvent::RmrkMarket(crate::Event::CollectionOfferPlaced {
+			offerer: BOB,
+			collection_id: COLLECTION_ID_0,
+			price: MIN_OFFER_ON_NFT,
+			asset_id: None,
+		}));
+		assert_eq!(Balances::reserved_balance(BOB), MIN_OFFER_ON_NFT);
+		// BOB cannot make a second collection offer on the collection
+		assert_noop!(
+			RmrkMarket::make_collection_offer(
+				Origin::signed(BOB),
+				COLLECTION_ID_0,
+				MIN_OFFER_ON_NFT,
+				None,
+				None,
+				None,
+			),
+			Error::<Test>::AlreadyOffered
+		);
+		// NFT [0,0] is not red
+		assert_noop!(
+			RmrkMarket::accept_collection_offer(
+				Origin::signed(ALICE),
+				COLLECTION_ID_0,
+				NFT_ID_0,
+				BOB
+			),
+			Error::<Test>::PropertyMismatch
+		);
+		// CHARLIE cannot accept the offer for ALICE's NFT
+		assert_noop!(
+			RmrkMarket::accept_collection_offer(
+				Origin::signed(CHARLIE),
+				COLLECTION_ID_0,
+				NFT_ID_1,
+				BOB
+			),
+			Error::<Test>::NoPermission
+		);
+		// ALICE makes NFT [0,1] red and accepts the offer for it
+		assert_ok!(RmrkCore::set_property(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			Some(NFT_ID_1),
+			stbk("color"),
+			stb("red"),
+		));
+		assert_ok!(RmrkMarket::accept_collection_offer(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			NFT_ID_1,
+			BOB
+		));
+		System::assert_last_event(MockEvent::RmrkMarket(crate::Event::CollectionOfferAccepted {
+			owner: ALICE,
+			buyer: BOB,
+			collection_id: COLLECTION_ID_0,
+			nft_id: NFT_ID_1,
+		}));
+		assert_eq!(Uniques::owner(COLLECTION_ID_0, NFT_ID_1), Some(BOB));
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		// The offer covered a single NFT
+		assert!(RmrkMarket::collection_offers(COLLECTION_ID_0, BOB).is_none());
+		// CHARLIE offers on any NFT of the collection and withdraws the offer
+		assert_ok!(RmrkMarket::make_collection_offer(
+			Origin::signed(CHARLIE),
+			COLLECTION_ID_0,
+			MIN_OFFER_ON_NFT,
+			None,
+			None,
+			None,
+		));
+		assert_ok!(RmrkMarket::withdraw_collection_offer(Origin::signed(CHARLIE), COLLECTION_ID_0));
+		System::assert_last_event(MockEvent::RmrkMarket(crate::Event::CollectionOfferWithdrawn {
+			offerer: CHARLIE,
+			collection_id: COLLECTION_ID_0,
+		}));
+		assert_eq!(Balances::reserved_balance(CHARLIE), 0);
+		// The withdrawn offer cannot be accepted
+		assert_noop!(
+			RmrkMarket::accept_collection_offer(
+				Origin::signed(ALICE),
+				COLLECTION_ID_0,
+				NFT_ID_0,
+				CHARLIE
+			),
+			Error::<Test>::UnknownOffer
+		);
+	});
+}
+
+#[test]
+fn bundle_works() {
+	new_test_ext().execute_with(|| {
+		// Create a basic collection
+		assert_ok!(basic_collection());
+		// Mint four NFTs
+		for _ in 0..4 {
+			assert_ok!(basic_mint());
+		}
+		// Bundles must contain at least one NFT and at most MaxBundleSize NFTs
+		assert_noop!(
+			RmrkMarket::list_bundle(Origin::signed(ALICE), vec![], 100u128, None, None),
+			Error::<Test>::EmptyBundle
+		);
+		assert_noop!(
+			RmrkMarket::list_bundle(
+				Origin::signed(ALICE),
+				vec![(0, 0), (0, 1), (0, 2), (0, 3)],
+				100u128,
+				None,
+				None
+			),
+			Error::<Test>::BundleTooLarge
+		);
+		// Bundles cannot contain the same NFT twice
+		assert_noop!(
+			RmrkMarket::list_bundle(
+				Origin::signed(ALICE),
+				vec![(0, 0), (0, 0)],
+				100u128,
+				None,
+				None
+			),
+			Error::<Test>::DuplicateBundleItem
+		);
+		// BOB cannot bundle ALICE's NFTs
+		assert_noop!(
+			RmrkMarket::list_bundle(Origin::signed(BOB), vec![(0, 0), (0, 1)], 100u128, None, None),
+			Error::<Test>::NoPermission
+		);
+		// ALICE bundles NFTs [0,0] and [0,1]
+		assert_ok!(RmrkMarket::list_bundle(
+			Origin::signed(ALICE),
+			vec![(0, 0), (0, 1)],
+			100u128,
+			None,
+			None
+		));
+		System::assert_last_event(MockEvent::RmrkMarket(crate::Event::BundleListed {
+			owner: ALICE,
+			bundle_id: 0,
+			price: 100u128,
+			asset_id: None,
+		}));
+		// Bundled NFTs are locked
+		assert_noop!(
+			RmrkCore::send(
+				Origin::signed(ALICE),
+				COLLECTION_ID_0,
+				NFT_ID_1,
+				AccountIdOrCollectionNftTuple::AccountId(CHARLIE),
+			),
+			pallet_rmrk_core::Error::<Test>::NftIsLocked
+		);
+		// The owner cannot buy the bundle
+		assert_noop!(
+			RmrkMarket::buy_bundle(Origin::signed(ALICE), 0, None),
+			Error::<Test>::CannotBuyOwnToken
+		);
+		// BOB must pay the listed price
+		assert_noop!(
+			RmrkMarket::buy_bundle(Origin::signed(BOB), 0, Some(50u128)),
+			Error::<Test>::PriceDiffersFromExpected
+		);
+		// BOB buys the bundle
+		let alice_balance = Balances::free_balance(ALICE);
+		assert_ok!(RmrkMarket::buy_bundle(Origin::signed(BOB), 0, Some(100u128)));
+		System::assert_last_event(MockEvent::RmrkMarket(crate::Event::BundleSold {
+			owner: ALICE,
+			buyer: BOB,
+			bundle_id: 0,
+			price: 100u128,
+		}));
+		assert_eq!(Uniques::owner(COLLECTION_ID_0, NFT_ID_0), Some(BOB));
+		assert_eq!(Uniques::owner(COLLECTION_ID_0, NFT_ID_1), Some(BOB));
+		assert_eq!(Balances::free_balance(ALICE), alice_balance + 100u128);
+		assert!(RmrkMarket::bundles(0).is_none());
+		assert!(RmrkMarket::bundled_nfts(COLLECTION_ID_0, NFT_ID_0).is_none());
+		assert!(RmrkCore::locks(COLLECTION_ID_0, NFT_ID_1).is_none());
+		// ALICE bundles NFTs [0,2] and [0,3]
+		assert_ok!(RmrkMarket::list_bundle(
+			Origin::signed(ALICE),
+			vec![(0, 2), (0, 3)],
+			100u128,
+			None,
+			None
+		));
+		// Listing NFT [0,2] separately invalidates the bundle
+		assert_ok!(RmrkMarket::list(Origin::signed(ALICE), COLLECTION_ID_0, 2, 10u128, None, None));
+		System::assert_has_event(MockEvent::RmrkMarket(crate::Event::BundleUnlisted {
+			owner: ALICE,
+			bundle_id: 1,
+		}));
+		assert!(RmrkMarket::bundles(1).is_none());
+		assert!(RmrkCore::locks(COLLECTION_ID_0, 3).is_none());
+		assert_noop!(
+			RmrkMarket::buy_bundle(Origin::signed(BOB), 1, None),
+			Error::<Test>::UnknownBundle
+		);
+		// ALICE bundles NFT [0,3] and unlists it, BOB cannot
+		assert_ok!(RmrkMarket::list_bundle(
+			Origin::signed(ALICE),
+			vec![(0, 3)],
+			10u128,
+			None,
+			None
+		));
+		assert_noop!(
+			RmrkMarket::unlist_bundle(Origin::signed(BOB), 2),
+			Error::<Test>::NoPermission
+		);
+		assert_ok!(RmrkMarket::unlist_bundle(Origin::signed(ALICE), 2));
+		System::assert_last_event(MockEvent::RmrkMarket(crate::Event::BundleUnlisted {
+			owner: ALICE,
+			bundle_id: 2,
+		}));
+		// ALICE bundles NFT [0,3] until block 3
+		assert_ok!(RmrkMarket::list_bundle(
+			Origin::signed(ALICE),
+			vec![(0, 3)],
+			10u128,
+			Some(3),
+			None
+		));
+		System::set_block_number(3);
+		// The bundle has expired
+		assert_noop!(
+			RmrkMarket::buy_bundle(Origin::signed(BOB), 3, None),
+			Error::<Test>::ListingHasExpired
+		);
+		// Sending NFT [0,3] invalidates the bundle
+		assert_ok!(RmrkCore::send(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			3,
+			AccountIdOrCollectionNftTuple::AccountId(CHARLIE),
+		));
+		assert!(RmrkMarket::bundles(3).is_none());
+		assert!(RmrkMarket::bundled_nfts(COLLECTION_ID_0, 3).is_none());
+	});
+}
+
+#[test]
+fn expired_bundles_are_swept() {
+	new_test_ext().execute_with(|| {
+		// Create a basic collection
+		assert_ok!(basic_collection());
+		// Mint two NFTs
+		assert_ok!(basic_mint());
+		assert_ok!(basic_mint());
+		// ALICE bundles NFT [0,0] until block 5 and NFT [0,1] without expiry
+		assert_ok!(RmrkMarket::list_bundle(
+			Origin::signed(ALICE),
+			vec![(0, 0)],
+			10u128,
+			Some(5),
+			None
+		));
+		assert_ok!(RmrkMarket::list_bundle(
+			Origin::signed(ALICE),
+			vec![(0, 1)],
+			10u128,
+			None,
+			None
+		));
+		// At block 5 the sweep removes the expired bundle and unlocks its NFT
+		System::set_block_number(5);
+		RmrkMarket::on_idle(5, u64::MAX);
+		RmrkMarket::on_idle(5, u64::MAX);
+		assert!(RmrkMarket::bundles(0).is_none());
+		assert!(RmrkMarket::bundled_nfts(COLLECTION_ID_0, NFT_ID_0).is_none());
+		assert!(RmrkCore::locks(COLLECTION_ID_0, NFT_ID_0).is_none());
+		System::assert_has_event(MockEvent::RmrkMarket(crate::Event::BundleExpired {
+			owner: ALICE,
+			bundle_id: 0,
+		}));
+		// The bundle without expiry is kept
+		assert!(RmrkMarket::bundles(1).is_some());
+		assert!(RmrkCore::locks(COLLECTION_ID_0, NFT_ID_1).is_some());
+	});
+}
+
+#[test]
+fn nested_bundle_works() {
+	new_test_ext().execute_with(|| {
+		// Create a basic collection
+		assert_ok!(basic_collection());
+		// Mint three NFTs
+		assert_ok!(basic_mint());
+		assert_ok!(basic_mint());
+		assert_ok!(basic_mint());
+		// ALICE sends NFT [0,1] to NFT [0,0]
+		assert_ok!(RmrkCore::send(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			NFT_ID_1,
+			AccountIdOrCollectionNftTuple::CollectionAndNftTuple(COLLECTION_ID_0, NFT_ID_0),
+		));
+		// ALICE cannot bundle NFT [0,1] with the NFT it is nested in
+		assert_noop!(
+			RmrkMarket::list_bundle(
+				Origin::signed(ALICE),
+				vec![(0, 0), (0, 1)],
+				100u128,
+				None,
+				None
+			),
+			Error::<Test>::NestedBundleItem
+		);
+		// BOB cannot bundle ALICE's nested NFT
+		assert_noop!(
+			RmrkMarket::list_bundle(Origin::signed(BOB), vec![(0, 1)], 100u128, None, None),
+			Error::<Test>::NoPermission
+		);
+		// ALICE bundles NFTs [0,1] and [0,2] as the root owner
+		assert_ok!(RmrkMarket::list_bundle(
+			Origin::signed(ALICE),
+			vec![(0, 1), (0, 2)],
+			100u128,
+			None,
+			None
+		));
+		// BOB buys the bundle, NFT [0,1] is sent to BOB
+		assert_ok!(RmrkMarket::buy_bundle(Origin::signed(BOB), 0, Some(100u128)));
+		assert_eq!(Uniques::owner(COLLECTION_ID_0, NFT_ID_1), Some(BOB));
+		assert_eq!(Uniques::owner(COLLECTION_ID_0, 2), Some(BOB));
+		assert!(
+			RmrkCore::children((COLLECTION_ID_0, NFT_ID_0), (COLLECTION_ID_0, NFT_ID_1)).is_none()
+		);
+	});
+}
+
+#[test]
+fn swap_works() {
+	new_test_ext().execute_with(|| {
+		// Create a basic collection
+		assert_ok!(basic_collection());
+		// Mint NFTs [0,0] and [0,1] for ALICE and NFT [0,2] for BOB
+		assert_ok!(basic_mint());
+		assert_ok!(basic_mint());
+		assert_ok!(RmrkCore::mint_nft(
+			Origin::signed(ALICE),
+			BOB,
+			COLLECTION_ID_0,
+			Some(ALICE),
+			Some(Permill::from_float(1.525)),
+			bvec![0u8; 20],
+		));
+		// ALICE sends NFT [0,1] to NFT [0,0]
+		assert_ok!(RmrkCore::send(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			NFT_ID_1,
+			AccountIdOrCollectionNftTuple::CollectionAndNftTuple(COLLECTION_ID_0, NFT_ID_0),
+		));
+		// A swap cannot be proposed to self
+		assert_noop!(
+			RmrkMarket::propose_swap(
+				Origin::signed(ALICE),
+				ALICE,
+				vec![(0, 0)],
+				vec![(0, 1)],
+				0,
+				None
+			),
+			Error::<Test>::CannotSwapWithSelf
+		);
+		// A swap must request an NFT
+		assert_noop!(
+			RmrkMarket::propose_swap(Origin::signed(ALICE), BOB, vec![(0, 0)], vec![], 0, None),
+			Error::<Test>::EmptySwap
+		);
+		// ALICE cannot request an NFT BOB doesn't own
+		assert_noop!(
+			RmrkMarket::propose_swap(
+				Origin::signed(ALICE),
+				BOB,
+				vec![(0, 1)],
+				vec![(0, 0)],
+				0,
+				None
+			),
+			Error::<Test>::NoPermission
+		);
+		// ALICE cannot offer NFT [0,1] along with the NFT it is nested in
+		assert_noop!(
+			RmrkMarket::propose_swap(
+				Origin::signed(ALICE),
+				BOB,
+				vec![(0, 0), (0, 1)],
+				vec![(0, 2)],
+				0,
+				None
+			),
+			Error::<Test>::NestedSwapItem
+		);
+		// BOB cannot request NFT [0,1] along with the NFT it is nested in
+		assert_noop!(
+			RmrkMarket::propose_swap(
+				Origin::signed(BOB),
+				ALICE,
+				vec![(0, 2)],
+				vec![(0, 1), (0, 0)],
+				0,
+				None
+			),
+			Error::<Test>::NestedSwapItem
+		);
+		// ALICE offers the nested NFT [0,1] plus 10 UNITS for BOB's NFT [0,2]
+		let alice_reserved = Balances::reserved_balance(ALICE);
+		assert_ok!(RmrkMarket::propose_swap(
+			Origin::signed(ALICE),
+			BOB,
+			vec![(0, 1)],
+			vec![(0, 2)],
+			10 * UNITS,
+			None
+		));
+		System::assert_last_event(MockEvent::RmrkMarket(crate::Event::SwapProposed {
+			proposer: ALICE,
+			counterparty: BOB,
+			swap_id: 0,
+		}));
+		// The offered NFT is locked and the amount reserved
+		assert!(RmrkCore::locks(COLLECTION_ID_0, NFT_ID_1).is_some());
+		assert_eq!(Balances::reserved_balance(ALICE), alice_reserved + 10 * UNITS);
+		// Only BOB can accept the swap
+		assert_noop!(
+			RmrkMarket::accept_swap(Origin::signed(CHARLIE), 0),
+			Error::<Test>::NoPermission
+		);
+		let bob_balance = Balances::free_balance(BOB);
+		assert_ok!(RmrkMarket::accept_swap(Origin::signed(BOB), 0));
+		System::assert_last_event(MockEvent::RmrkMarket(crate::Event::SwapAccepted {
+			proposer: ALICE,
+			counterparty: BOB,
+			swap_id: 0,
+		}));
+		assert_eq!(Uniques::owner(COLLECTION_ID_0, NFT_ID_1), Some(BOB));
+		assert_eq!(Uniques::owner(COLLECTION_ID_0, 2), Some(ALICE));
+		assert_eq!(Balances::free_balance(BOB), bob_balance + 10 * UNITS);
+		assert_eq!(Balances::reserved_balance(ALICE), alice_reserved);
+		assert!(RmrkCore::locks(COLLECTION_ID_0, NFT_ID_1).is_none());
+		assert!(RmrkMarket::swaps(0).is_none());
+		// ALICE offers NFT [0,0] for NFT [0,1] and BOB rejects the swap
+		assert_ok!(RmrkMarket::propose_swap(
+			Origin::signed(ALICE),
+			BOB,
+			vec![(0, 0)],
+			vec![(0, 1)],
+			0,
+			None
+		));
+		assert_noop!(
+			RmrkMarket::cancel_swap(Origin::signed(CHARLIE), 1),
+			Error::<Test>::NoPermission
+		);
+		assert_ok!(RmrkMarket::cancel_swap(Origin::signed(BOB), 1));
+		System::assert_last_event(MockEvent::RmrkMarket(crate::Event::SwapCancelled {
+			swap_id: 1,
+		}));
+		assert!(RmrkCore::locks(COLLECTION_ID_0, NFT_ID_0).is_none());
+		assert_noop!(RmrkMarket::accept_swap(Origin::signed(BOB), 1), Error::<Test>::UnknownSwap);
+	});
+}
+
+#[test]
+fn nested_listing_works() {
+	new_test_ext().execute_with(|| {
+		// Create a basic collection
+		assert_ok!(basic_collection());
+		// Mint two NFTs
+		assert_ok!(basic_mint());
+		assert_ok!(basic_mint());
+		// ALICE sends NFT [0,1] to NFT [0,0]
+		assert_ok!(RmrkCore::send(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			NFT_ID_1,
+			AccountIdOrCollectionNftTuple::CollectionAndNftTuple(COLLECTION_ID_0, NFT_ID_0),
+		));
+		// ALICE lists NFT [0,1] as its root owner
+		assert_ok!(RmrkMarket::list(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			NFT_ID_1,
+			10u128,
+			None,
+			None,
+		));
+		System::assert_last_event(MockEvent::RmrkMarket(crate::Event::TokenListed {
+			owner: ALICE,
+			collection_id: COLLECTION_ID_0,
+			nft_id: NFT_ID_1,
+			price: 10u128,
+			asset_id: None,
+		}));
+		// ALICE cannot send NFT [0,0] along with the listed NFT [0,1]
+		assert_noop!(
+			RmrkCore::send(
+				Origin::signed(ALICE),
+				COLLECTION_ID_0,
+				NFT_ID_0,
+				AccountIdOrCollectionNftTuple::AccountId(CHARLIE),
+			),
+			pallet_rmrk_core::Error::<Test>::NftIsLocked
+		);
+		// ALICE cannot burn NFT [0,0] along with the listed NFT [0,1]
+		assert_noop!(
+			RmrkCore::burn_nft(Origin::signed(ALICE), COLLECTION_ID_0, NFT_ID_0),
+			pallet_rmrk_core::Error::<Test>::NftIsLocked
+		);
+		// BOB buys NFT [0,1], which is detached from NFT [0,0]
+		let alice_balance = Balances::free_balance(ALICE);
+		assert_ok!(RmrkMarket::buy(
+			Origin::signed(BOB),
+			COLLECTION_ID_0,
+			NFT_ID_1,
+			Some(10u128),
+			None
+		));
+		System::assert_last_event(MockEvent::RmrkMarket(crate::Event::TokenSold {
+			owner: ALICE,
+			buyer: BOB,
+			collection_id: COLLECTION_ID_0,
+			nft_id: NFT_ID_1,
+			price: 10u128,
+		}));
+		assert_eq!(Uniques::owner(COLLECTION_ID_0, NFT_ID_1), Some(BOB));
+		assert_eq!(
+			RmrkCore::nfts(COLLECTION_ID_0, NFT_ID_1).unwrap().owner,
+			AccountIdOrCollectionNftTuple::AccountId(BOB)
+		);
+		assert_eq!(RmrkCore::children_count((COLLECTION_ID_0, NFT_ID_0)), 0);
+		assert_eq!(Balances::free_balance(ALICE), alice_balance + 10u128);
+	});
+}
+
+#[test]
+fn buying_equipped_nft_unequips_it() {
+	new_test_ext().execute_with(|| {
+		// Create a basic collection
+		assert_ok!(basic_collection());
+		// Mint NFT [0,0] as the equipper and NFT [0,1] as the item
+		assert_ok!(basic_mint());
+		assert_ok!(basic_mint());
+		// ALICE equips NFT [0,1] in NFT [0,0]
+		equip_nft_1_in_nft_0();
+		assert!(RmrkCore::nfts(COLLECTION_ID_0, NFT_ID_1).unwrap().equipped);
+		// ALICE lists the equipped NFT [0,1]
+		assert_ok!(RmrkMarket::list(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			NFT_ID_1,
+			10u128,
+			None,
+			None,
+		));
+		// BOB buys NFT [0,1], which is unequipped as it leaves NFT [0,0]
+		assert_ok!(RmrkMarket::buy(
+			Origin::signed(BOB),
+			COLLECTION_ID_0,
+			NFT_ID_1,
+			Some(10u128),
+			None
+		));
+		System::assert_has_event(MockEvent::RmrkEquip(pallet_rmrk_equip::Event::SlotUnequipped {
+			item_collection: COLLECTION_ID_0,
+			item_nft: NFT_ID_1,
+			base_id: 0,
+			slot_id: 201,
+		}));
+		assert!(!RmrkCore::nfts(COLLECTION_ID_0, NFT_ID_1).unwrap().equipped);
+		assert!(RmrkEquip::equippings(((COLLECTION_ID_0, NFT_ID_0), 0, 201)).is_none());
+		assert_eq!(Uniques::owner(COLLECTION_ID_0, NFT_ID_1), Some(BOB));
+	});
+}
+
+#[test]
+fn swapping_equipped_nft_unequips_it() {
+	new_test_ext().execute_with(|| {
+		// Create a basic collection
+		assert_ok!(basic_collection());
+		// Mint NFT [0,0] as the equipper and NFT [0,1] as the item
+		assert_ok!(basic_mint());
+		assert_ok!(basic_mint());
+		// ALICE equips NFT [0,1] in NFT [0,0]
+		equip_nft_1_in_nft_0();
+		assert!(RmrkCore::nfts(COLLECTION_ID_0, NFT_ID_1).unwrap().equipped);
+		// Mint NFT [0,2] for BOB
+		assert_ok!(RmrkCore::mint_nft(
+			Origin::signed(ALICE),
+			BOB,
+			COLLECTION_ID_0,
+			Some(ALICE),
+			Some(Permill::from_float(1.525)),
+			bvec![0u8; 20],
+		));
+		// ALICE offers the equipped NFT [0,1] for NFT [0,2] and BOB accepts
+		assert_ok!(RmrkMarket::propose_swap(
+			Origin::signed(ALICE),
+			BOB,
+			vec![(0, 1)],
+			vec![(0, 2)],
+			0,
+			None
+		));
+		assert_ok!(RmrkMarket::accept_swap(Origin::signed(BOB), 0));
+		// NFT [0,1] is unequipped as it leaves NFT [0,0]
+		assert!(!RmrkCore::nfts(COLLECTION_ID_0, NFT_ID_1).unwrap().equipped);
+		assert!(RmrkEquip::equippings(((COLLECTION_ID_0, NFT_ID_0), 0, 201)).is_none());
+		assert_eq!(Uniques::owner(COLLECTION_ID_0, NFT_ID_1), Some(BOB));
+	});
+}
+
+#[test]
+fn private_listing_works() {
+	new_test_ext().execute_with(|| {
+		// Create a basic collection
+		assert_ok!(basic_collection());
+		// Mint an NFT
+		assert_ok!(basic_mint());
+		// Private listing needs at least one buyer
+		assert_noop!(
+			RmrkMarket::list_private(
+				Origin::signed(ALICE),
+				COLLECTION_ID_0,
+				NFT_ID_0,
+				10u128,
+				None,
+				None,
+				vec![],
+			),
+			Error::<Test>::NoReservedBuyers
+		);
+		// Private listing is reserved for at most MaxReservedBuyers buyers
+		assert_noop!(
+			RmrkMarket::list_private(
+				Origin::signed(ALICE),
+				COLLECTION_ID_0,
+				NFT_ID_0,
+				10u128,
+				None,
+				None,
+				vec![BOB, CHARLIE, FEE_ACCOUNT],
+			),
+			Error::<Test>::TooManyReservedBuyers
+		);
+		// BOB cannot list ALICE's NFT privately
+		assert_noop!(
+			RmrkMarket::list_private(
+				Origin::signed(BOB),
+				COLLECTION_ID_0,
+				NFT_ID_0,
+				10u128,
+				None,
+				None,
+				vec![BOB],
+			),
+			Error::<Test>::NoPermission
+		);
+		// ALICE lists NFT [0,0] for BOB only
+		assert_ok!(RmrkMarket::list_private(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			NFT_ID_0,
+			10u128,
+			None,
+			None,
+			vec![BOB],
+		));
+		System::assert_last_event(MockEvent::RmrkMarket(crate::Event::TokenListedPrivately {
+			owner: ALICE,
+			collection_id: COLLECTION_ID_0,
+			nft_id: NFT_ID_0,
+			price: 10u128,
+			asset_id: None,
+			buyers: vec![BOB],
+		}));
+		// CHARLIE cannot buy NFT [0,0] as the listing is reserved for BOB
+		assert_noop!(
+			RmrkMarket::buy(Origin::signed(CHARLIE), COLLECTION_ID_0, NFT_ID_0, Some(10u128), None),
+			Error::<Test>::NotReservedBuyer
+		);
+		// BOB buys NFT [0,0]
+		assert_ok!(RmrkMarket::buy(
+			Origin::signed(BOB),
+			COLLECTION_ID_0,
+			NFT_ID_0,
+			Some(10u128),
+			None
+		));
+		System::assert_last_event(MockEvent::RmrkMarket(crate::Event::TokenSold {
+			owner: ALICE,
+			buyer: BOB,
+			collection_id: COLLECTION_ID_0,
+			nft_id: NFT_ID_0,
+			price: 10u128,
+		}));
+		assert_eq!(Uniques::owner(COLLECTION_ID_0, NFT_ID_0), Some(BOB));
+		assert_eq!(RmrkMarket::listed_nfts(COLLECTION_ID_0, NFT_ID_0), None);
+	});
+}
+
+#[test]
+fn collection_stats_work() {
+	new_test_ext().execute_with(|| {
+		// Create a basic collection
+		assert_ok!(basic_collection());
+		// Mint three NFTs
+		assert_ok!(basic_mint());
+		assert_ok!(basic_mint());
+		assert_ok!(basic_mint());
+		// No sales and no listings yet
+		assert_eq!(RmrkMarket::collection_stats(COLLECTION_ID_0, None), None);
+		assert_eq!(RmrkMarket::collection_floor(COLLECTION_ID_0, None), None);
+		// ALICE lists the NFTs at 10, 20 and 30
+		for (nft_id, price) in [(0, 10u128), (1, 20u128), (2, 30u128)] {
+			assert_ok!(RmrkMarket::list(
+				Origin::signed(ALICE),
+				COLLECTION_ID_0,
+				nft_id,
+				price,
+				None,
+				None,
+			));
+		}
+		assert_eq!(RmrkMarket::collection_floor(COLLECTION_ID_0, None), Some(10u128));
+		// Private listings do not count towards the floor
+		assert_ok!(RmrkMarket::list_private(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			NFT_ID_0,
+			5u128,
+			None,
+			None,
+			vec![BOB],
+		));
+		assert_eq!(RmrkMarket::collection_floor(COLLECTION_ID_0, None), Some(20u128));
+		// Listings in another asset do not count towards the floor
+		assert_eq!(RmrkMarket::collection_floor(COLLECTION_ID_0, Some(ASSET_ID_0)), None);
+		// BOB buys the NFTs in three blocks
+		for (block, nft_id, price) in [(1, 0, 5u128), (2, 1, 20u128), (3, 2, 30u128)] {
+			run_to_block(block);
+			assert_ok!(RmrkMarket::buy(
+				Origin::signed(BOB),
+				COLLECTION_ID_0,
+				nft_id,
+				Some(price),
+				None
+			));
+		}
+		assert_eq!(RmrkMarket::collection_floor(COLLECTION_ID_0, None), None);
+		// Only the last MaxRecentSales sales are kept
+		let stats = RmrkMarket::collection_stats(COLLECTION_ID_0, None).unwrap();
+		assert_eq!(stats.last_price, 30u128);
+		assert_eq!(stats.volume, 55u128);
+		assert_eq!(stats.sale_count, 3);
+		assert_eq!(
+			stats.recent_sales.into_inner(),
+			vec![
+				SaleRecord { nft_id: NFT_ID_1, price: 20u128, block: 2 },
+				SaleRecord { nft_id: 2, price: 30u128, block: 3 },
+			]
+		);
+		// Sales in the native currency are not counted for other assets
+		assert_eq!(RmrkMarket::collection_stats(COLLECTION_ID_0, Some(ASSET_ID_0)), None);
+	});
+}
+
+#[test]
+fn escrowed_listing_works() {
+	new_test_ext().execute_with(|| {
+		// Create a basic collection
+		assert_ok!(basic_collection());
+		// Mint an NFT
+		assert_ok!(basic_mint());
+		// BOB cannot list ALICE's NFT in escrow
+		assert_noop!(
+			RmrkMarket::list_escrowed(
+				Origin::signed(BOB),
+				COLLECTION_ID_0,
+				NFT_ID_0,
+				10u128,
+				None,
+				None,
+			),
+			Error::<Test>::NoPermission
+		);
+		// ALICE lists NFT [0,0] in escrow
+		assert_ok!(RmrkMarket::list_escrowed(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			NFT_ID_0,
+			10u128,
+			None,
+			None,
+		));
+		System::assert_last_event(MockEvent::RmrkMarket(crate::Event::TokenListedEscrowed {
+			owner: ALICE,
+			collection_id: COLLECTION_ID_0,
+			nft_id: NFT_ID_0,
+			price: 10u128,
+			asset_id: None,
+		}));
+		// The pallet holds NFT [0,0], which stays visible to ALICE
+		assert_eq!(
+			Uniques::owner(COLLECTION_ID_0, NFT_ID_0),
+			Some(RmrkMarket::nft_escrow_account())
+		);
+		assert_eq!(RmrkMarket::escrowed_nfts_of(&ALICE), vec![(COLLECTION_ID_0, NFT_ID_0)]);
+		// ALICE cannot send NFT [0,0] while it is in escrow
+		assert_noop!(
+			RmrkCore::send(
+				Origin::signed(ALICE),
+				COLLECTION_ID_0,
+				NFT_ID_0,
+				AccountIdOrCollectionNftTuple::AccountId(CHARLIE),
+			),
+			pallet_rmrk_core::Error::<Test>::NoPermission
+		);
+		// BOB cannot unlist NFT [0,0]
+		assert_noop!(
+			RmrkMarket::unlist(Origin::signed(BOB), COLLECTION_ID_0, NFT_ID_0),
+			Error::<Test>::NoPermission
+		);
+		// ALICE unlists NFT [0,0], which returns it to ALICE
+		assert_ok!(RmrkMarket::unlist(Origin::signed(ALICE), COLLECTION_ID_0, NFT_ID_0));
+		System::assert_last_event(MockEvent::RmrkMarket(crate::Event::TokenUnlisted {
+			owner: ALICE,
+			collection_id: COLLECTION_ID_0,
+			nft_id: NFT_ID_0,
+		}));
+		assert_eq!(Uniques::owner(COLLECTION_ID_0, NFT_ID_0), Some(ALICE));
+		assert!(RmrkMarket::escrowed_nfts_of(&ALICE).is_empty());
+		// ALICE lists NFT [0,0] in escrow again
+		assert_ok!(RmrkMarket::list_escrowed(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			NFT_ID_0,
+			10u128,
+			None,
+			None,
+		));
+		// ALICE cannot buy NFT [0,0] from the escrow
+		assert_noop!(
+			RmrkMarket::buy(Origin::signed(ALICE), COLLECTION_ID_0, NFT_ID_0, Some(10u128), None),
+			Error::<Test>::CannotBuyOwnToken
+		);
+		// BOB buys NFT [0,0] and ALICE is paid
+		let alice_balance = Balances::free_balance(ALICE);
+		assert_ok!(RmrkMarket::buy(
+			Origin::signed(BOB),
+			COLLECTION_ID_0,
+			NFT_ID_0,
+			Some(10u128),
+			None
+		));
+		System::assert_last_event(MockEvent::RmrkMarket(crate::Event::TokenSold {
+			owner: ALICE,
+			buyer: BOB,
+			collection_id: COLLECTION_ID_0,
+			nft_id: NFT_ID_0,
+			price: 10u128,
+		}));
+		assert_eq!(Uniques::owner(COLLECTION_ID_0, NFT_ID_0), Some(BOB));
+		assert!(RmrkMarket::escrowed_nfts_of(&ALICE).is_empty());
+		assert_eq!(Balances::free_balance(ALICE), alice_balance + 10u128);
+	});
+}
+
+#[test]
+fn expired_escrowed_listing_is_returned() {
+	new_test_ext().execute_with(|| {
+		// Create a basic collection
+		assert_ok!(basic_collection());
+		// Mint an NFT
+		assert_ok!(basic_mint());
+		// ALICE lists NFT [0,0] in escrow until block 5
+		assert_ok!(RmrkMarket::list_escrowed(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			NFT_ID_0,
+			10u128,
+			Some(5),
+			None,
+		));
+		// The sweep returns NFT [0,0] to ALICE once the listing has expired
+		System::set_block_number(5);
+		RmrkMarket::on_idle(5, u64::MAX);
+		System::assert_last_event(MockEvent::RmrkMarket(crate::Event::ListingExpired {
+			owner: ALICE,
+			collection_id: COLLECTION_ID_0,
+			nft_id: NFT_ID_0,
+		}));
+		assert_eq!(Uniques::owner(COLLECTION_ID_0, NFT_ID_0), Some(ALICE));
+		assert!(RmrkMarket::escrowed_nfts_of(&ALICE).is_empty());
+		assert_eq!(RmrkMarket::listed_nfts(COLLECTION_ID_0, NFT_ID_0), None);
+	});
+}
+
+#[test]
+fn offers_are_kept_in_escrow() {
+	new_test_ext().execute_with(|| {
+		// Create a basic collection
+		assert_ok!(basic_collection());
+		// Mint an NFT
+		assert_ok!(basic_mint());
+		// BOB offers on NFT [0,0]
+		assert_ok!(RmrkMarket::make_offer(
+			Origin::signed(BOB),
+			COLLECTION_ID_0,
+			NFT_ID_0,
+			MIN_OFFER_ON_NFT,
+			None,
+			None,
+			None
+		));
+		// ALICE lists NFT [0,0] in escrow and unlists it, BOB's offer is kept
+		assert_ok!(RmrkMarket::list_escrowed(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			NFT_ID_0,
+			10u128,
+			None,
+			None,
+		));
+		assert!(RmrkMarket::offers((COLLECTION_ID_0, NFT_ID_0), BOB).is_some());
+		// ALICE cannot offer on NFT [0,0] while it is in escrow
+		assert_noop!(
+			RmrkMarket::make_offer(
+				Origin::signed(ALICE),
+				COLLECTION_ID_0,
+				NFT_ID_0,
+				MIN_OFFER_ON_NFT,
+				None,
+				None,
+				None
+			),
+			Error::<Test>::CannotOfferOnOwnToken
+		);
+		assert_ok!(RmrkMarket::unlist(Origin::signed(ALICE), COLLECTION_ID_0, NFT_ID_0));
+		assert!(RmrkMarket::offers((COLLECTION_ID_0, NFT_ID_0), BOB).is_some());
+		assert_eq!(Balances::reserved_balance(BOB), MIN_OFFER_ON_NFT);
+		// ALICE lists NFT [0,0] in escrow again and accepts BOB's offer
+		assert_ok!(RmrkMarket::list_escrowed(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			NFT_ID_0,
+			10u128,
+			None,
+			None,
+		));
+		assert_ok!(RmrkMarket::accept_offer(Origin::signed(ALICE), COLLECTION_ID_0, NFT_ID_0, BOB));
+		assert_eq!(Uniques::owner(COLLECTION_ID_0, NFT_ID_0), Some(BOB));
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+	});
+}
+
+#[test]
+fn offers_are_refunded_on_escrow_sale() {
+	new_test_ext().execute_with(|| {
+		// Create a basic collection
+		assert_ok!(basic_collection());
+		// Mint an NFT
+		assert_ok!(basic_mint());
+		// BOB offers on NFT [0,0]
+		assert_ok!(RmrkMarket::make_offer(
+			Origin::signed(BOB),
+			COLLECTION_ID_0,
+			NFT_ID_0,
+			MIN_OFFER_ON_NFT,
+			None,
+			None,
+			None
+		));
+		// ALICE lists NFT [0,0] in escrow, BOB's offer is kept
+		assert_ok!(RmrkMarket::list_escrowed(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			NFT_ID_0,
+			10u128,
+			None,
+			None,
+		));
+		assert_eq!(Balances::reserved_balance(BOB), MIN_OFFER_ON_NFT);
+		// CHARLIE buys NFT [0,0] out of escrow
+		assert_ok!(RmrkMarket::buy(Origin::signed(CHARLIE), COLLECTION_ID_0, NFT_ID_0, None, None));
+		assert_eq!(Uniques::owner(COLLECTION_ID_0, NFT_ID_0), Some(CHARLIE));
+		// BOB's offer on the sold NFT is refunded
+		assert!(RmrkMarket::offers((COLLECTION_ID_0, NFT_ID_0), BOB).is_none());
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+	});
+}
+
+#[test]
+fn pause_works() {
+	new_test_ext().execute_with(|| {
+		// Create a basic collection
+		assert_ok!(basic_collection());
+		// Mint two NFTs
+		assert_ok!(basic_mint());
+		assert_ok!(basic_mint());
+		// ALICE lists NFT [0,0]
+		assert_ok!(RmrkMarket::list(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			NFT_ID_0,
+			10u128,
+			None,
+			None,
+		));
+		// BOB cannot pause the collection of ALICE
+		assert_noop!(
+			RmrkMarket::set_collection_paused(Origin::signed(BOB), COLLECTION_ID_0, true),
+			Error::<Test>::NoPermission
+		);
+		// ALICE pauses the collection as its issuer
+		assert_ok!(RmrkMarket::set_collection_paused(Origin::signed(ALICE), COLLECTION_ID_0, true));
+		System::assert_last_event(MockEvent::RmrkMarket(crate::Event::CollectionPauseSet {
+			collection_id: COLLECTION_ID_0,
+			paused: true,
+		}));
+		// Trading the collection fails while paused
+		assert_noop!(
+			RmrkMarket::buy(Origin::signed(BOB), COLLECTION_ID_0, NFT_ID_0, Some(10u128), None),
+			Error::<Test>::CollectionPaused
+		);
+		assert_noop!(
+			RmrkMarket::list(Origin::signed(ALICE), COLLECTION_ID_0, NFT_ID_1, 10u128, None, None),
+			Error::<Test>::CollectionPaused
+		);
+		assert_noop!(
+			RmrkMarket::make_offer(
+				Origin::signed(BOB),
+				COLLECTION_ID_0,
+				NFT_ID_1,
+				MIN_OFFER_ON_NFT,
+				None,
+				None,
+				None,
+			),
+			Error::<Test>::CollectionPaused
+		);
+		// The ProtocolOrigin resumes the collection
+		assert_ok!(RmrkMarket::set_collection_paused(Origin::root(), COLLECTION_ID_0, false));
+		// Only the ProtocolOrigin can halt the marketplace
+		assert_noop!(RmrkMarket::set_market_halted(Origin::signed(ALICE), true), BadOrigin);
+		assert_ok!(RmrkMarket::set_market_halted(Origin::root(), true));
+		System::assert_last_event(MockEvent::RmrkMarket(crate::Event::MarketHaltSet {
+			halted: true,
+		}));
+		// Trading fails while the marketplace is halted
+		assert_noop!(
+			RmrkMarket::buy(Origin::signed(BOB), COLLECTION_ID_0, NFT_ID_0, Some(10u128), None),
+			Error::<Test>::MarketHalted
+		);
+		// Listings can still be cancelled
+		assert_ok!(RmrkMarket::unlist(Origin::signed(ALICE), COLLECTION_ID_0, NFT_ID_0));
+		// Trading resumes once the marketplace is no longer halted
+		assert_ok!(RmrkMarket::set_market_halted(Origin::root(), false));
+		assert_ok!(RmrkMarket::list(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			NFT_ID_0,
+			10u128,
+			None,
+			None,
+		));
+		assert_ok!(RmrkMarket::buy(
+			Origin::signed(BOB),
+			COLLECTION_ID_0,
+			NFT_ID_0,
+			Some(10u128),
+			None
+		));
+		assert_eq!(Uniques::owner(COLLECTION_ID_0, NFT_ID_0), Some(BOB));
+	});
+}
+
+#[test]
+fn redeem_voucher_works() {
+	new_test_ext().execute_with(|| {
+		// The issuer signs vouchers off-chain with its key
+		let issuer_pair = sr25519::Pair::from_seed(&[7u8; 32]);
+		let issuer: AccountId32 = issuer_pair.public().into();
+		assert_ok!(Balances::transfer(Origin::signed(ALICE), issuer.clone(), 1_000 * UNITS));
+		// The issuer creates a collection
+		assert_ok!(RmrkCore::create_collection(
+			Origin::signed(issuer.clone()),
+			bvec![0u8; 20],
+			Some(5),
+			bvec![0u8; 15]
+		));
+		let voucher = MintVoucher {
+			collection_id: COLLECTION_ID_0,
+			metadata: bvec![1u8; 20],
+			royalty: Some(Permill::from_percent(5)),
+			price: 10u128,
+			asset_id: None,
+			nonce: 0,
+			expires: 10,
+		};
+		let signature = MultiSignature::from(issuer_pair.sign(&voucher_payload(&voucher)));
+		// A voucher signed by another account cannot be redeemed
+		let other_pair = sr25519::Pair::from_seed(&[8u8; 32]);
+		assert_noop!(
+			RmrkMarket::redeem_voucher(
+				Origin::signed(BOB),
+				voucher.clone(),
+				MultiSignature::from(other_pair.sign(&voucher_payload(&voucher))),
+			),
+			Error::<Test>::InvalidSignature
+		);
+		// A signature of the bare voucher, without the domain and chain, is rejected
+		assert_noop!(
+			RmrkMarket::redeem_voucher(
+				Origin::signed(BOB),
+				voucher.clone(),
+				MultiSignature::from(issuer_pair.sign(&voucher.encode())),
+			),
+			Error::<Test>::InvalidSignature
+		);
+		// A voucher cannot be altered
+		assert_noop!(
+			RmrkMarket::redeem_voucher(
+				Origin::signed(BOB),
+				MintVoucher { price: 1u128, ..voucher.clone() },
+				signature.clone(),
+			),
+			Error::<Test>::InvalidSignature
+		);
+		// BOB redeems the voucher, paying the price to the issuer
+		let bob_balance = Balances::free_balance(BOB);
+		assert_ok!(RmrkMarket::redeem_voucher(
+			Origin::signed(BOB),
+			voucher.clone(),
+			signature.clone()
+		));
+		System::assert_last_event(MockEvent::RmrkMarket(crate::Event::VoucherRedeemed {
+			issuer: issuer.clone(),
+			buyer: BOB,
+			collection_id: COLLECTION_ID_0,
+			nft_id: NFT_ID_0,
+			price: 10u128,
+			nonce: 0,
+		}));
+		assert_eq!(Balances::free_balance(BOB), bob_balance - 10u128);
+		assert_eq!(Uniques::owner(COLLECTION_ID_0, NFT_ID_0), Some(BOB));
+		let nft = RmrkCore::nfts(COLLECTION_ID_0, NFT_ID_0).unwrap();
+		assert_eq!(nft.recipient, issuer);
+		assert_eq!(nft.royalty, Permill::from_percent(5));
+		// The voucher cannot be redeemed twice
+		assert_noop!(
+			RmrkMarket::redeem_voucher(Origin::signed(CHARLIE), voucher, signature),
+			Error::<Test>::VoucherAlreadyRedeemed
+		);
+		// An expired voucher cannot be redeemed
+		let voucher = MintVoucher {
+			collection_id: COLLECTION_ID_0,
+			metadata: bvec![1u8; 20],
+			royalty: None,
+			price: 10u128,
+			asset_id: None,
+			nonce: 1,
+			expires: 5,
+		};
+		let signature = MultiSignature::from(issuer_pair.sign(&voucher_payload(&voucher)));
+		run_to_block(5);
+		assert_noop!(
+			RmrkMarket::redeem_voucher(Origin::signed(BOB), voucher, signature),
+			Error::<Test>::VoucherExpired
+		);
+	});
+}
+
+#[test]
+fn signed_orders_work() {
+	new_test_ext().execute_with(|| {
+		// The seller and the bidder sign orders off-chain with their keys
+		let seller_pair = sr25519::Pair::from_seed(&[7u8; 32]);
+		let seller: AccountId32 = seller_pair.public().into();
+		let bidder_pair = sr25519::Pair::from_seed(&[8u8; 32]);
+		let bidder: AccountId32 = bidder_pair.public().into();
+		assert_ok!(Balances::transfer(Origin::signed(ALICE), seller.clone(), 1_000 * UNITS));
+		assert_ok!(Balances::transfer(Origin::signed(ALICE), bidder.clone(), 1_000 * UNITS));
+		// Create a basic collection
+		assert_ok!(basic_collection());
+		// ALICE mints NFT [0,0] for the seller
+		assert_ok!(RmrkCore::mint_nft(
+			Origin::signed(ALICE),
+			seller.clone(),
+			COLLECTION_ID_0,
+			Some(seller.clone()),
+			Some(Permill::from_percent(5)),
+			bvec![0u8; 20],
+		));
+		let listing = Order {
+			kind: OrderKind::Listing,
+			maker: seller.clone(),
+			collection_id: COLLECTION_ID_0,
+			nft_id: NFT_ID_0,
+			price: 10u128,
+			asset_id: None,
+			expires: 10,
+			nonce: 0,
+		};
+		let signature = MultiSignature::from(seller_pair.sign(&order_payload(&listing)));
+		// An order cannot be altered
+		assert_noop!(
+			RmrkMarket::fill_order(
+				Origin::signed(BOB),
+				Order { price: 1u128, ..listing.clone() },
+				signature.clone(),
+			),
+			Error::<Test>::InvalidSignature
+		);
+		// A signature of the bare order, without the domain and chain, is rejected
+		assert_noop!(
+			RmrkMarket::fill_order(
+				Origin::signed(BOB),
+				listing.clone(),
+				MultiSignature::from(seller_pair.sign(&listing.encode())),
+			),
+			Error::<Test>::InvalidSignature
+		);
+		// BOB fills the listing of the seller
+		let seller_balance = Balances::free_balance(&seller);
+		assert_ok!(RmrkMarket::fill_order(Origin::signed(BOB), listing.clone(), signature.clone()));
+		System::assert_last_event(MockEvent::RmrkMarket(crate::Event::OrderFilled {
+			maker: seller.clone(),
+			taker: BOB,
+			collection_id: COLLECTION_ID_0,
+			nft_id: NFT_ID_0,
+			price: 10u128,
+			nonce: 0,
+		}));
+		assert_eq!(Uniques::owner(COLLECTION_ID_0, NFT_ID_0), Some(BOB));
+		assert_eq!(Balances::free_balance(&seller), seller_balance + 10u128);
+		// The listing cannot be filled twice
+		assert_noop!(
+			RmrkMarket::fill_order(Origin::signed(CHARLIE), listing, signature),
+			Error::<Test>::InvalidOrderNonce
+		);
+		// The bidder bids on NFT [0,0]
+		let bid = Order {
+			kind: OrderKind::Bid,
+			maker: bidder.clone(),
+			collection_id: COLLECTION_ID_0,
+			nft_id: NFT_ID_0,
+			price: 20u128,
+			asset_id: None,
+			expires: 10,
+			nonce: 0,
+		};
+		let signature = MultiSignature::from(bidder_pair.sign(&order_payload(&bid)));
+		// Only the owner of NFT [0,0] can fill the bid
+		assert_noop!(
+			RmrkMarket::fill_order(Origin::signed(CHARLIE), bid.clone(), signature.clone()),
+			Error::<Test>::NoPermission
+		);
+		assert_ok!(RmrkMarket::fill_order(Origin::signed(BOB), bid, signature));
+		assert_eq!(Uniques::owner(COLLECTION_ID_0, NFT_ID_0), Some(bidder.clone()));
+		// The bidder cancels the bid with nonce 1
+		let bid = Order {
+			kind: OrderKind::Bid,
+			maker: bidder.clone(),
+			collection_id: COLLECTION_ID_0,
+			nft_id: NFT_ID_0,
+			price: 20u128,
+			asset_id: None,
+			expires: 10,
+			nonce: 1,
+		};
+		let signature = MultiSignature::from(bidder_pair.sign(&order_payload(&bid)));
+		assert_ok!(RmrkMarket::cancel_order(Origin::signed(bidder.clone()), 1));
+		System::assert_last_event(MockEvent::RmrkMarket(crate::Event::OrderCancelled {
+			maker: bidder.clone(),
+			nonce: 1,
+		}));
+		assert_noop!(
+			RmrkMarket::fill_order(Origin::signed(BOB), bid, signature),
+			Error::<Test>::InvalidOrderNonce
+		);
+		// The bidder cancels all orders below nonce 5
+		assert_ok!(RmrkMarket::bump_order_nonce(Origin::signed(bidder.clone()), 5));
+		System::assert_last_event(MockEvent::RmrkMarket(crate::Event::OrderNonceBumped {
+			maker: bidder.clone(),
+			min_nonce: 5,
+		}));
+		assert_noop!(
+			RmrkMarket::bump_order_nonce(Origin::signed(bidder.clone()), 5),
+			Error::<Test>::InvalidOrderNonce
+		);
+		assert_noop!(
+			RmrkMarket::cancel_order(Origin::signed(bidder.clone()), 3),
+			Error::<Test>::InvalidOrderNonce
+		);
+		// An expired listing cannot be filled
+		let listing = Order {
+			kind: OrderKind::Listing,
+			maker: bidder.clone(),
+			collection_id: COLLECTION_ID_0,
+			nft_id: NFT_ID_0,
+			price: 10u128,
+			asset_id: None,
+			expires: 5,
+			nonce: 5,
+		};
+		let signature = MultiSignature::from(bidder_pair.sign(&order_payload(&listing)));
+		run_to_block(5);
+		assert_noop!(
+			RmrkMarket::fill_order(Origin::signed(BOB), listing, signature),
+			Error::<Test>::OrderExpired
+		);
+	});
+}
+
+#[test]
+fn buy_for_recipient_works() {
+	new_test_ext().execute_with(|| {
+		// Create a basic collection
+		assert_ok!(basic_collection());
+		// ALICE mints NFT [0,0] for ALICE, [0,1] for BOB and [0,2] for CHARLIE
+		assert_ok!(basic_mint());
+		for owner in [BOB, CHARLIE] {
+			assert_ok!(RmrkCore::mint_nft(
+				Origin::signed(ALICE),
+				owner,
+				COLLECTION_ID_0,
+				Some(ALICE),
+				None,
+				bvec![0u8; 20],
+			));
+		}
+		// ALICE lists NFT [0,0]
+		assert_ok!(RmrkMarket::list(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			NFT_ID_0,
+			10u128,
+			None,
+			None,
+		));
+		// BOB buys NFT [0,0] straight into NFT [0,1]
+		assert_ok!(RmrkMarket::buy(
+			Origin::signed(BOB),
+			COLLECTION_ID_0,
+			NFT_ID_0,
+			Some(10u128),
+			Some(AccountIdOrCollectionNftTuple::CollectionAndNftTuple(COLLECTION_ID_0, NFT_ID_1)),
+		));
+		System::assert_has_event(MockEvent::RmrkMarket(crate::Event::TokenSold {
+			owner: ALICE,
+			buyer: BOB,
+			collection_id: COLLECTION_ID_0,
+			nft_id: NFT_ID_0,
+			price: 10u128,
+		}));
+		assert_eq!(
+			RmrkCore::nfts(COLLECTION_ID_0, NFT_ID_0).unwrap().owner,
+			AccountIdOrCollectionNftTuple::CollectionAndNftTuple(COLLECTION_ID_0, NFT_ID_1)
+		);
+		assert!(
+			RmrkCore::children((COLLECTION_ID_0, NFT_ID_1), (COLLECTION_ID_0, NFT_ID_0)).is_some()
+		);
+		// BOB lists NFT [0,0] as its root owner
+		assert_ok!(RmrkMarket::list(
+			Origin::signed(BOB),
+			COLLECTION_ID_0,
+			NFT_ID_0,
+			10u128,
+			None,
+			None,
+		));
+		// ALICE buys NFT [0,0] for NFT [0,2], which CHARLIE has to accept
+		assert_ok!(RmrkMarket::buy(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			NFT_ID_0,
+			Some(10u128),
+			Some(AccountIdOrCollectionNftTuple::CollectionAndNftTuple(COLLECTION_ID_0, 2)),
+		));
+		assert!(RmrkCore::pending_nfts(COLLECTION_ID_0, NFT_ID_0).is_some());
+		assert!(RmrkCore::nfts(COLLECTION_ID_0, NFT_ID_0).is_none());
+		// CHARLIE cannot have NFT [0,1] delivered anywhere CHARLIE does not own
+		assert_noop!(
+			RmrkMarket::make_offer(
+				Origin::signed(CHARLIE),
+				COLLECTION_ID_0,
+				NFT_ID_1,
+				MIN_OFFER_ON_NFT,
+				None,
+				None,
+				Some(AccountIdOrCollectionNftTuple::AccountId(ALICE)),
+			),
+			Error::<Test>::NoPermission
+		);
+		// CHARLIE makes an offer on NFT [0,1] of BOB, delivered into NFT [0,2] of CHARLIE
+		assert_ok!(RmrkMarket::make_offer(
+			Origin::signed(CHARLIE),
+			COLLECTION_ID_0,
+			NFT_ID_1,
+			MIN_OFFER_ON_NFT,
+			None,
+			None,
+			Some(AccountIdOrCollectionNftTuple::CollectionAndNftTuple(COLLECTION_ID_0, 2)),
+		));
+		// BOB accepts the offer and NFT [0,1] is delivered to the recipient of the offer
+		assert_ok!(RmrkMarket::accept_offer(
+			Origin::signed(BOB),
+			COLLECTION_ID_0,
+			NFT_ID_1,
+			CHARLIE,
+		));
+		assert_eq!(
+			RmrkCore::nfts(COLLECTION_ID_0, NFT_ID_1).unwrap().owner,
+			AccountIdOrCollectionNftTuple::CollectionAndNftTuple(COLLECTION_ID_0, 2)
+		);
+	});
+}
+
+#[test]
+fn update_offer_works() {
+	new_test_ext().execute_with(|| {
+		// Create a basic collection
+		assert_ok!(basic_collection());
+		// Mint an NFT
+		assert_ok!(basic_mint());
+		// BOB makes an offer on NFT [0,0]
+		assert_ok!(RmrkMarket::make_offer(
+			Origin::signed(BOB),
+			COLLECTION_ID_0,
+			NFT_ID_0,
+			MIN_OFFER_ON_NFT,
+			None,
+			None,
+			None,
+		));
+		assert_eq!(Balances::reserved_balance(BOB), MIN_OFFER_ON_NFT);
+		// CHARLIE has no offer to update
+		assert_noop!(
+			RmrkMarket::update_offer(
+				Origin::signed(CHARLIE),
+				COLLECTION_ID_0,
+				NFT_ID_0,
+				MIN_OFFER_ON_NFT,
+				None,
+				None,
+			),
+			Error::<Test>::UnknownOffer
+		);
+		// BOB raises the offer, reserving the difference
+		assert_ok!(RmrkMarket::update_offer(
+			Origin::signed(BOB),
+			COLLECTION_ID_0,
+			NFT_ID_0,
+			2 * MIN_OFFER_ON_NFT,
+			None,
+			None,
+		));
+		System::assert_last_event(MockEvent::RmrkMarket(crate::Event::OfferUpdated {
+			offerer: BOB,
+			collection_id: COLLECTION_ID_0,
+			nft_id: NFT_ID_0,
+			price: 2 * MIN_OFFER_ON_NFT,
+			expires: None,
+		}));
+		assert_eq!(Balances::reserved_balance(BOB), 2 * MIN_OFFER_ON_NFT);
+		// BOB cannot lower the offer below the minimum
+		assert_noop!(
+			RmrkMarket::update_offer(
+				Origin::signed(BOB),
+				COLLECTION_ID_0,
+				NFT_ID_0,
+				MIN_OFFER_ON_NFT - 1,
+				None,
+				None,
+			),
+			Error::<Test>::OfferTooLow
+		);
+		// BOB cannot have the NFT delivered to CHARLIE
+		assert_noop!(
+			RmrkMarket::update_offer(
+				Origin::signed(BOB),
+				COLLECTION_ID_0,
+				NFT_ID_0,
+				MIN_OFFER_ON_NFT,
+				None,
+				Some(Some(AccountIdOrCollectionNftTuple::AccountId(CHARLIE))),
+			),
+			Error::<Test>::NoPermission
+		);
+		// BOB lowers the offer, sets an expiration and a recipient, unreserving the difference
+		assert_ok!(RmrkMarket::update_offer(
+			Origin::signed(BOB),
+			COLLECTION_ID_0,
+			NFT_ID_0,
+			MIN_OFFER_ON_NFT,
+			Some(Some(10)),
+			Some(Some(AccountIdOrCollectionNftTuple::AccountId(BOB))),
+		));
+		assert_eq!(Balances::reserved_balance(BOB), MIN_OFFER_ON_NFT);
+		let offer = RmrkMarket::offers((COLLECTION_ID_0, NFT_ID_0), BOB).unwrap();
+		assert_eq!(offer.amount, MIN_OFFER_ON_NFT);
+		assert_eq!(offer.expires, Some(10));
+		assert_eq!(offer.recipient, Some(AccountIdOrCollectionNftTuple::AccountId(BOB)));
+		// BOB cannot update the offer once it has expired
+		System::set_block_number(10);
+		assert_noop!(
+			RmrkMarket::update_offer(
+				Origin::signed(BOB),
+				COLLECTION_ID_0,
+				NFT_ID_0,
+				2 * MIN_OFFER_ON_NFT,
+				None,
+				None,
+			),
+			Error::<Test>::OfferHasExpired
+		);
+	});
+}
+
+#[test]
+fn migrate_listings_and_offers_to_v1_works() {
+	new_test_ext().execute_with(|| {
+		use crate::types::ListInfo;
+		use codec::Encode;
+		use frame_support::{
+			storage::migration::put_storage_value,
+			traits::{GetStorageVersion, StorageVersion},
+			Blake2_128Concat, StorageHasher,
+		};
+		StorageVersion::new(0).put::<RmrkMarket>();
+		// NFT (0, 0) is listed by ALICE and has an offer from BOB in the old layout
+		let listing_key = [
+			Blake2_128Concat::hash(&COLLECTION_ID_0.encode()),
+			Blake2_128Concat::hash(&NFT_ID_0.encode()),
+		]
+		.concat();
+		put_storage_value(b"RmrkMarket", b"ListedNfts", &listing_key, (ALICE, 10u128, Some(5u64)));
+		let offer_key = [
+			Blake2_128Concat::hash(&(COLLECTION_ID_0, NFT_ID_0).encode()),
+			Blake2_128Concat::hash(&BOB.encode()),
+		]
+		.concat();
+		put_storage_value(b"RmrkMarket", b"Offers", &offer_key, (BOB, 5u128, None::<u64>));
+		// Migrate to v1
+		crate::migration::migrate_to_v1::<Test>();
+		// The listing and offer are in the native currency and counted
+		assert_eq!(
+			RmrkMarket::listed_nfts(COLLECTION_ID_0, NFT_ID_0),
+			Some(ListInfo {
+				listed_by: ALICE,
+				amount: 10u128,
+				asset_id: None,
+				expires: Some(5u64),
+				dutch: None,
+				buyers: None,
+			})
+		);
+		assert_eq!(
+			RmrkMarket::offers((COLLECTION_ID_0, NFT_ID_0), BOB),
+			Some(Offer {
+				maker: BOB,
+				amount: 5u128,
+				asset_id: None,
+				expires: None,
+				recipient: None,
+			})
+		);
+		assert_eq!(RmrkMarket::offers_count((COLLECTION_ID_0, NFT_ID_0)), 1);
+		assert_eq!(RmrkMarket::on_chain_storage_version(), 1);
+	});
+}
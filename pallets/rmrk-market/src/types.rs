@@ -4,25 +4,177 @@ use frame_support::pallet_prelude::*;
 use serde::{Deserialize, Serialize};
 
 use scale_info::TypeInfo;
+use sp_runtime::Permill;
+
+use rmrk_traits::{
+	primitives::{CollectionId, NftId},
+	AccountIdOrCollectionNftTuple,
+};
 
 #[derive(Encode, Decode, Eq, Copy, PartialEq, Clone, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
-pub struct ListInfo<AccountId, Balance, BlockNumber> {
+pub struct ListInfo<AccountId, Balance, BlockNumber, AssetId, BoundedBuyers> {
 	/// Owner who listed the NFT at the time
 	pub(super) listed_by: AccountId,
 	/// Listed amount
 	pub(super) amount: Balance,
+	/// Asset the amount is in, the native currency if `None`
+	pub(super) asset_id: Option<AssetId>,
 	/// After this block the listing can't be bought
 	pub(super) expires: Option<BlockNumber>,
+	/// Declining price of a Dutch auction listing, `amount` is then the start price
+	pub(super) dutch: Option<DutchAuction<Balance, BlockNumber>>,
+	/// Accounts the listing is reserved for, anyone can buy it if `None`
+	pub(super) buyers: Option<BoundedBuyers>,
+}
+
+#[derive(Encode, Decode, Eq, Copy, PartialEq, Clone, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct DutchAuction<Balance, BlockNumber> {
+	/// Lowest price, reached at `end`
+	pub(super) floor_price: Balance,
+	/// Block at which the price starts to decline
+	pub(super) start: BlockNumber,
+	/// Block at which the price reaches the floor
+	pub(super) end: BlockNumber,
+	/// Number of blocks between price drops, the price declines every block if zero
+	pub(super) step: BlockNumber,
 }
 
 #[derive(Encode, Decode, Eq, Copy, PartialEq, Clone, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
-pub struct Offer<AccountId, Balance, BlockNumber> {
+pub struct Offer<AccountId, Balance, BlockNumber, AssetId> {
 	/// User who made the offer
 	pub(super) maker: AccountId,
 	/// Offered amount
 	pub(super) amount: Balance,
+	/// Asset the amount is in, the native currency if `None`
+	pub(super) asset_id: Option<AssetId>,
+	/// After this block the offer can't be accepted
+	pub(super) expires: Option<BlockNumber>,
+	/// Account or NFT of the maker receiving the NFT instead of the maker's account
+	pub(super) recipient: Option<AccountIdOrCollectionNftTuple<AccountId>>,
+}
+
+#[derive(Encode, Decode, Eq, PartialEq, Clone, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct CollectionOffer<AccountId, Balance, BlockNumber, AssetId, BoundedKey, BoundedValue> {
+	/// User who made the offer
+	pub(super) maker: AccountId,
+	/// Amount offered for a single NFT of the collection
+	pub(super) amount: Balance,
+	/// Asset the amount is in, the native currency if `None`
+	pub(super) asset_id: Option<AssetId>,
 	/// After this block the offer can't be accepted
 	pub(super) expires: Option<BlockNumber>,
+	/// Property key and value an NFT must have to be sold to the offer
+	pub(super) property: Option<(BoundedKey, BoundedValue)>,
+}
+
+/// Bundle id
+pub type BundleId = u32;
+
+#[derive(Encode, Decode, Eq, PartialEq, Clone, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct BundleInfo<AccountId, Balance, BlockNumber, AssetId, BoundedItems> {
+	/// Owner who listed the NFTs at the time
+	pub(super) listed_by: AccountId,
+	/// Collection and NFT ids of the NFTs sold together
+	pub(super) items: BoundedItems,
+	/// Listed amount for all NFTs
+	pub(super) amount: Balance,
+	/// Asset the amount is in, the native currency if `None`
+	pub(super) asset_id: Option<AssetId>,
+	/// After this block the bundle can't be bought
+	pub(super) expires: Option<BlockNumber>,
+}
+
+/// Swap id
+pub type SwapId = u32;
+
+#[derive(Encode, Decode, Eq, PartialEq, Clone, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct SwapInfo<AccountId, Balance, AssetId, BoundedItems> {
+	/// User who proposed the swap
+	pub(super) proposer: AccountId,
+	/// User who can accept the swap
+	pub(super) counterparty: AccountId,
+	/// Collection and NFT ids of the NFTs offered by the proposer
+	pub(super) offered: BoundedItems,
+	/// Collection and NFT ids of the NFTs requested from the counterparty
+	pub(super) requested: BoundedItems,
+	/// Amount paid by the proposer next to the offered NFTs
+	pub(super) amount: Balance,
+	/// Asset the amount is in, the native currency if `None`
+	pub(super) asset_id: Option<AssetId>,
+}
+
+#[derive(Encode, Decode, Eq, Copy, PartialEq, Clone, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct AuctionInfo<AccountId, Balance, BlockNumber, AssetId> {
+	/// Owner who put the NFT up for auction
+	pub(super) owner: AccountId,
+	/// Minimum amount of the first bid
+	pub(super) reserve_price: Balance,
+	/// Asset the bids are in, the native currency if `None`
+	pub(super) asset_id: Option<AssetId>,
+	/// Minimum amount by which a bid must exceed the current highest bid
+	pub(super) min_increment: Balance,
+	/// Current highest bidder and the amount reserved for the bid
+	pub(super) highest_bid: Option<(AccountId, Balance)>,
+	/// At this block the auction is settled
+	pub(super) end: BlockNumber,
+}
+
+/// Mint voucher signed off-chain by the issuer of a collection. Whoever redeems it pays the price
+/// to the issuer and receives the NFT minted from it.
+#[derive(Encode, Decode, Eq, PartialEq, Clone, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct MintVoucher<Balance, BlockNumber, AssetId, BoundedString> {
+	/// Collection the NFT is minted in
+	pub collection_id: CollectionId,
+	/// Arbitrary data about the NFT, e.g. IPFS hash
+	pub metadata: BoundedString,
+	/// Royalty paid to the issuer on later sales
+	pub royalty: Option<Permill>,
+	/// Price of the NFT
+	pub price: Balance,
+	/// Asset the price is in, the native currency if `None`
+	pub asset_id: Option<AssetId>,
+	/// Number used once per issuer, to prevent redeeming a voucher twice
+	pub nonce: u64,
+	/// From this block the voucher can't be redeemed
+	pub expires: BlockNumber,
+}
+
+/// Side of an order signed off-chain
+#[derive(Encode, Decode, Eq, Copy, PartialEq, Clone, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum OrderKind {
+	/// The maker sells the NFT for the price
+	Listing,
+	/// The maker buys the NFT for the price
+	Bid,
+}
+
+/// Listing or bid signed off-chain by its maker, filled on-chain by the counterparty
+#[derive(Encode, Decode, Eq, PartialEq, Clone, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Order<AccountId, Balance, BlockNumber, AssetId> {
+	/// Whether the maker sells or buys the NFT
+	pub kind: OrderKind,
+	/// User who signed the order
+	pub maker: AccountId,
+	/// Collection id of the NFT
+	pub collection_id: CollectionId,
+	/// NFT id of the NFT
+	pub nft_id: NftId,
+	/// Price of the NFT
+	pub price: Balance,
+	/// Asset the price is in, the native currency if `None`
+	pub asset_id: Option<AssetId>,
+	/// From this block the order can't be filled
+	pub expires: BlockNumber,
+	/// Number used once per maker, to cancel the order or prevent filling it twice
+	pub nonce: u64,
 }
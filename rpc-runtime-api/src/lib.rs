@@ -0,0 +1,44 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unnecessary_mut_passed)]
+
+use codec::Codec;
+use rmrk_traits::primitives::{CollectionId, NftId};
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+	/// Queries of the RMRK NFTs, e.g. for wallets to show the NFTs of an account
+	pub trait NftApi<AccountId>
+	where
+		AccountId: Codec,
+	{
+		/// NFTs an account is the root owner of, nested NFTs included, a page at a time. The next
+		/// page starts after the last NFT of the previous one.
+		fn account_nfts(
+			owner: AccountId,
+			start_after: Option<(CollectionId, NftId)>,
+			limit: u32,
+		) -> Vec<(CollectionId, NftId)>;
+	}
+
+	/// Queries of the RMRK market, e.g. for wallets to show collection prices without an indexer
+	pub trait MarketApi<AccountId, AssetId, Balance, CollectionStats>
+	where
+		AccountId: Codec,
+		AssetId: Codec,
+		Balance: Codec,
+		CollectionStats: Codec,
+	{
+		/// Sale statistics of a collection for sales in an asset, the native currency if `None`
+		fn collection_stats(
+			collection_id: CollectionId,
+			asset_id: Option<AssetId>,
+		) -> Option<CollectionStats>;
+
+		/// Lowest price an NFT of a collection is listed at in an asset, the native currency if
+		/// `None`
+		fn collection_floor(collection_id: CollectionId, asset_id: Option<AssetId>) -> Option<Balance>;
+
+		/// NFTs listed by an account that are held in escrow until sold or unlisted
+		fn escrowed_nfts(owner: AccountId) -> Vec<(CollectionId, NftId)>;
+	}
+}
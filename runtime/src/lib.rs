@@ -10,7 +10,10 @@ use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
 use sp_runtime::{
 	create_runtime_str, generic, impl_opaque_keys,
-	traits::{AccountIdLookup, BlakeTwo256, Block as BlockT, IdentifyAccount, NumberFor, Verify},
+	traits::{
+		AccountIdConversion, AccountIdLookup, BlakeTwo256, Block as BlockT, IdentifyAccount,
+		NumberFor, Verify,
+	},
 	transaction_validity::{TransactionSource, TransactionValidity},
 	ApplyExtrinsicResult, MultiSignature,
 };
@@ -27,7 +30,7 @@ pub use frame_support::{
 		constants::{BlockExecutionWeight, ExtrinsicBaseWeight, RocksDbWeight, WEIGHT_PER_SECOND},
 		IdentityFee, Weight,
 	},
-	StorageValue,
+	PalletId, StorageValue,
 };
 pub use pallet_balances::Call as BalancesCall;
 pub use pallet_timestamp::Call as TimestampCall;
@@ -43,6 +46,7 @@ pub use pallet_rmrk_core;
 
 pub use pallet_rmrk_equip;
 pub use pallet_rmrk_market;
+use rmrk_traits::primitives::{CollectionId, NftId};
 
 /// An index to a block.
 pub type BlockNumber = u32;
@@ -119,7 +123,7 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	//   `spec_version`, and `authoring_version` are the same between Wasm and native.
 	// This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
 	//   the compatible custom types.
-	spec_version: 100,
+	spec_version: 101,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
 	transaction_version: 1,
@@ -284,6 +288,30 @@ parameter_types! {
 	pub OperationalFeeMultiplier: u8 = 5;
 }
 
+parameter_types! {
+	pub const AssetDeposit: Balance = 100 * DOLLARS;
+	pub const ApprovalDeposit: Balance = DOLLARS;
+	pub const AssetsStringLimit: u32 = 50;
+	pub const MetadataDepositBase: Balance = 10 * DOLLARS;
+	pub const MetadataDepositPerByte: Balance = DOLLARS;
+}
+
+impl pallet_assets::Config for Runtime {
+	type Event = Event;
+	type Balance = Balance;
+	type AssetId = u32;
+	type Currency = Balances;
+	type ForceOrigin = frame_system::EnsureRoot<AccountId>;
+	type AssetDeposit = AssetDeposit;
+	type MetadataDepositBase = MetadataDepositBase;
+	type MetadataDepositPerByte = MetadataDepositPerByte;
+	type ApprovalDeposit = ApprovalDeposit;
+	type StringLimit = AssetsStringLimit;
+	type Freezer = ();
+	type Extra = ();
+	type WeightInfo = pallet_assets::weights::SubstrateWeight<Runtime>;
+}
+
 impl pallet_transaction_payment::Config for Runtime {
 	type OnChargeTransaction = CurrencyAdapter<Balances, ()>;
 	type TransactionByteFee = TransactionByteFee;
@@ -306,6 +334,9 @@ parameter_types! {
 	pub const MaxRecursions: u32 = 10;
 	pub const ResourceSymbolLimit: u32 = 10;
 	pub const CollectionSymbolLimit: u32 = 100;
+	pub const ReturnRejectedNfts: bool = false;
+	pub const MaxChildrenPerNft: u32 = 100;
+	pub const MaxSubtreeSize: u32 = 50;
 }
 
 impl pallet_rmrk_core::Config for Runtime {
@@ -314,10 +345,25 @@ impl pallet_rmrk_core::Config for Runtime {
 	type MaxRecursions = MaxRecursions;
 	type ResourceSymbolLimit = ResourceSymbolLimit;
 	type CollectionSymbolLimit = CollectionSymbolLimit;
+	type OnNftChange = RmrkMarket;
+	type ReturnRejectedNfts = ReturnRejectedNfts;
+	type MaxChildrenPerNft = MaxChildrenPerNft;
+	type MaxSubtreeSize = MaxSubtreeSize;
 }
 
 parameter_types! {
 	pub const MinimumOfferAmount: Balance = UNITS / 10_000;
+	pub const MaxOffersPerNft: u32 = 50;
+	pub const MarketplaceFee: Permill = Permill::from_percent(2);
+	pub const MarketPalletId: PalletId = PalletId(*b"rmrk/mkt");
+	pub const MarketFeePalletId: PalletId = PalletId(*b"rmrk/fee");
+	pub MarketplaceFeeAccount: AccountId = MarketFeePalletId::get().into_account();
+	pub const AuctionExtensionPeriod: BlockNumber = 10 * MINUTES;
+	pub const MaxAuctionsPerBlock: u32 = 50;
+	pub const MaxSweepsPerBlock: u32 = 100;
+	pub const MaxBundleSize: u32 = 20;
+	pub const MaxReservedBuyers: u32 = 10;
+	pub const MaxRecentSales: u32 = 20;
 }
 
 impl pallet_rmrk_market::Config for Runtime {
@@ -325,6 +371,21 @@ impl pallet_rmrk_market::Config for Runtime {
 	type ProtocolOrigin = frame_system::EnsureRoot<AccountId>;
 	type Currency = Balances;
 	type MinimumOfferAmount = MinimumOfferAmount;
+	type MaxOffersPerNft = MaxOffersPerNft;
+	type MarketplaceFee = MarketplaceFee;
+	type MarketplaceFeeAccount = MarketplaceFeeAccount;
+	type AuctionExtensionPeriod = AuctionExtensionPeriod;
+	type MaxAuctionsPerBlock = MaxAuctionsPerBlock;
+	type MaxSweepsPerBlock = MaxSweepsPerBlock;
+	type MaxBundleSize = MaxBundleSize;
+	type MaxReservedBuyers = MaxReservedBuyers;
+	type MaxRecentSales = MaxRecentSales;
+	type Equip = RmrkEquip;
+	type AssetId = u32;
+	type Assets = Assets;
+	type PalletId = MarketPalletId;
+	type OffchainSignature = Signature;
+	type OffchainPublic = <Signature as Verify>::Signer;
 }
 
 parameter_types! {
@@ -383,6 +444,7 @@ construct_runtime!(
 		Aura: pallet_aura,
 		Grandpa: pallet_grandpa,
 		Balances: pallet_balances,
+		Assets: pallet_assets,
 		TransactionPayment: pallet_transaction_payment,
 		Sudo: pallet_sudo,
 		// Include the custom logic from the pallet-template in the runtime.
@@ -553,6 +615,42 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl rmrk_rpc_runtime_api::NftApi<Block, AccountId> for Runtime {
+		fn account_nfts(
+			owner: AccountId,
+			start_after: Option<(CollectionId, NftId)>,
+			limit: u32,
+		) -> Vec<(CollectionId, NftId)> {
+			RmrkCore::nfts_of_owner(&owner, start_after, limit)
+		}
+	}
+
+	impl rmrk_rpc_runtime_api::MarketApi<
+		Block,
+		AccountId,
+		u32,
+		Balance,
+		pallet_rmrk_market::CollectionStatsOf<Runtime>,
+	> for Runtime {
+		fn collection_stats(
+			collection_id: CollectionId,
+			asset_id: Option<u32>,
+		) -> Option<pallet_rmrk_market::CollectionStatsOf<Runtime>> {
+			RmrkMarket::collection_stats(collection_id, asset_id)
+		}
+
+		fn collection_floor(
+			collection_id: CollectionId,
+			asset_id: Option<u32>,
+		) -> Option<Balance> {
+			RmrkMarket::collection_floor(collection_id, asset_id)
+		}
+
+		fn escrowed_nfts(owner: AccountId) -> Vec<(CollectionId, NftId)> {
+			RmrkMarket::escrowed_nfts_of(&owner)
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn benchmark_metadata(extra: bool) -> (
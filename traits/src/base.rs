@@ -2,10 +2,10 @@ use super::{
 	part::{EquippableList, PartType},
 	theme::Theme,
 };
-use crate::primitives::{BaseId, SlotId};
+use crate::primitives::{BaseId, CollectionId, NftId, SlotId};
 use codec::{Decode, Encode};
 use scale_info::TypeInfo;
-use sp_runtime::{DispatchError, RuntimeDebug};
+use sp_runtime::{DispatchError, DispatchResult, RuntimeDebug};
 use sp_std::vec::Vec;
 
 #[cfg_attr(feature = "std", derive(PartialEq, Eq))]
@@ -48,3 +48,15 @@ pub trait Base<AccountId, CollectionId, NftId, BoundedString> {
 		theme: Theme<BoundedString>,
 	) -> Result<(), DispatchError>;
 }
+
+/// Unequips NFTs, e.g. before they leave the NFT they are equipped into.
+pub trait Unequip {
+	/// Unequip an NFT from the slot of its parent it is equipped in, if any
+	fn unequip_nft(collection_id: CollectionId, nft_id: NftId) -> DispatchResult;
+}
+
+impl Unequip for () {
+	fn unequip_nft(_collection_id: CollectionId, _nft_id: NftId) -> DispatchResult {
+		Ok(())
+	}
+}
@@ -2,6 +2,8 @@
 
 pub mod base;
 pub mod collection;
+pub mod lock;
+pub mod market;
 pub mod nft;
 pub mod part;
 pub mod priority;
@@ -9,12 +11,14 @@ pub mod property;
 pub mod resource;
 pub mod theme;
 
-pub use base::{Base, BaseInfo};
+pub use base::{Base, BaseInfo, Unequip};
 pub use part::{EquippableList, FixedPart, PartType, SlotPart};
 pub use theme::{Theme, ThemeProperty};
 // pub use part::{PartInfo};
 pub use collection::{Collection, CollectionInfo};
-pub use nft::{AccountIdOrCollectionNftTuple, Nft, NftInfo};
+pub use lock::{Lock, LockInfo, LockReason};
+pub use market::{CollectionStats, SaleRecord};
+pub use nft::{AccountIdOrCollectionNftTuple, Nft, NftInfo, NftRoot, OnNftChange};
 pub use priority::Priority;
 pub use property::Property;
 pub use resource::{Resource, ResourceInfo};
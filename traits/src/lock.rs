@@ -0,0 +1,49 @@
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use sp_runtime::{DispatchResult, RuntimeDebug};
+
+use frame_support::pallet_prelude::MaxEncodedLen;
+
+use crate::primitives::*;
+
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+/// Why an NFT is locked. While locked, an NFT cannot be sent, burned or modified.
+#[derive(Encode, Decode, Eq, PartialEq, Copy, Clone, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum LockReason {
+	/// Listed for sale on a marketplace
+	Listed,
+	/// Equipped into a slot of another NFT
+	Equipped,
+	/// Rented out to another account
+	Rented,
+	/// Offered in a pending swap
+	Swapped,
+	/// Application specific reason
+	Custom(u32),
+}
+
+/// Lock info.
+#[derive(Encode, Decode, Eq, PartialEq, Copy, Clone, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct LockInfo<BlockNumber> {
+	/// Why the NFT is locked
+	pub reason: LockReason,
+	/// From this block on the lock no longer applies
+	pub expires: Option<BlockNumber>,
+}
+
+/// Abstraction over a NFT lock system.
+pub trait Lock<BlockNumber> {
+	fn lock_nft(
+		collection_id: CollectionId,
+		nft_id: NftId,
+		reason: LockReason,
+		expires: Option<BlockNumber>,
+	) -> DispatchResult;
+	fn unlock_nft(collection_id: CollectionId, nft_id: NftId, reason: LockReason)
+		-> DispatchResult;
+	fn is_nft_locked(collection_id: CollectionId, nft_id: NftId) -> bool;
+}
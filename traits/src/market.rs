@@ -0,0 +1,36 @@
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+
+use frame_support::pallet_prelude::MaxEncodedLen;
+
+use crate::primitives::*;
+
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+/// Sale of an NFT, as kept in the recent sales of a collection.
+#[derive(Encode, Decode, Eq, PartialEq, Copy, Clone, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct SaleRecord<Balance, BlockNumber> {
+	/// The NFT that was sold
+	pub nft_id: NftId,
+	/// Price the NFT was sold at
+	pub price: Balance,
+	/// Block of the sale
+	pub block: BlockNumber,
+}
+
+/// Sale statistics of a collection, for sales in a single currency.
+#[derive(Encode, Decode, Eq, PartialEq, Clone, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct CollectionStats<Balance, BoundedSales> {
+	/// Price of the last sale
+	pub last_price: Balance,
+	/// Sum of the prices of all sales
+	pub volume: Balance,
+	/// Number of sales
+	pub sale_count: u64,
+	/// Most recent sales, oldest first
+	pub recent_sales: BoundedSales,
+}
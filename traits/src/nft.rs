@@ -12,13 +12,25 @@ use sp_std::result::Result;
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 
-#[derive(Encode, Decode, Eq, PartialEq, Copy, Clone, RuntimeDebug, TypeInfo)]
+#[derive(Encode, Decode, Eq, PartialEq, Copy, Clone, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum AccountIdOrCollectionNftTuple<AccountId> {
 	AccountId(AccountId),
 	CollectionAndNftTuple(CollectionId, NftId),
 }
 
+/// Root of the ownership tree an NFT is nested in
+#[derive(Encode, Decode, Eq, PartialEq, Clone, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct NftRoot<AccountId> {
+	/// The account at the root of the tree
+	pub owner: AccountId,
+	/// The NFT directly owned by `owner`, either the NFT itself or the one it is nested in
+	pub nft: (CollectionId, NftId),
+	/// Number of NFTs between the NFT and `owner`, 0 if owned by an account
+	pub depth: u32,
+}
+
 /// Nft info.
 #[cfg_attr(feature = "std", derive(PartialEq, Eq))]
 #[derive(Encode, Decode, RuntimeDebug, TypeInfo)]
@@ -70,4 +82,23 @@ pub trait Nft<AccountId, BoundedString> {
 		collection_id: CollectionId,
 		nft_id: NftId,
 	) -> Result<(AccountId, CollectionId, NftId), DispatchError>;
+	fn nft_cancel_send(
+		sender: AccountId,
+		collection_id: CollectionId,
+		nft_id: NftId,
+	) -> Result<(AccountId, CollectionId, NftId), DispatchError>;
 }
+
+/// Handler for NFTs changing owner or being burned, e.g. to clean up state tied to an NFT.
+pub trait OnNftChange {
+	/// Called after an NFT was sent to a new owner
+	fn on_nft_sent(_collection_id: CollectionId, _nft_id: NftId) {}
+	/// Called after an NFT was burned
+	fn on_nft_burned(_collection_id: CollectionId, _nft_id: NftId) {}
+	/// Worst-case weight of a call to `on_nft_sent` or `on_nft_burned`
+	fn weight() -> Weight {
+		0
+	}
+}
+
+impl OnNftChange for () {}